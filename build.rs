@@ -0,0 +1,45 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "ffi")]
+    write_header();
+}
+
+/// Generate `backpacker.h` in `OUT_DIR` from the C API in `src/ffi.rs`, and refresh the copy
+/// in `include/` only when `BACKPACKER_WRITE_HEADER` is set, so builds leave the source tree
+/// alone
+#[cfg(feature = "ffi")]
+fn write_header() {
+    use cbindgen::{Builder, Config, EnumConfig, ExportConfig, Language, RenameRule};
+
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-env-changed=BACKPACKER_WRITE_HEADER");
+    let dir = std::env::var("CARGO_MANIFEST_DIR").expect("Cargo sets the manifest directory");
+    let out_dir = std::env::var("OUT_DIR").expect("Cargo sets the output directory");
+
+    let config = Config {
+        language: Language::C,
+        include_guard: Some("BACKPACKER_H".to_string()),
+        header: Some("/* Generated from src/ffi.rs by build.rs. Don't edit. */".to_string()),
+        cpp_compat: true,
+        usize_is_size_t: true,
+        export: ExportConfig {
+            prefix: Some("Bp".to_string()),
+            ..ExportConfig::default()
+        },
+        enumeration: EnumConfig {
+            rename_variants: RenameRule::QualifiedScreamingSnakeCase,
+            ..EnumConfig::default()
+        },
+        ..Config::default()
+    };
+
+    let header = Builder::new()
+        .with_config(config)
+        .with_src(format!("{}/src/ffi.rs", dir))
+        .generate()
+        .expect("The C API can be turned into a header");
+    header.write_to_file(format!("{}/backpacker.h", out_dir));
+    if std::env::var_os("BACKPACKER_WRITE_HEADER").is_some() {
+        header.write_to_file(format!("{}/include/backpacker.h", dir));
+    }
+}
@@ -0,0 +1,154 @@
+/* Generated from src/ffi.rs by build.rs. Don't edit. */
+
+#ifndef BACKPACKER_H
+#define BACKPACKER_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * What went wrong in a call, or `Ok`
+ */
+typedef enum BpStatus {
+  BP_STATUS_OK = 0,
+  /**
+   * A pointer was null, or a string wasn't UTF-8 or the JSON expected
+   */
+  BP_STATUS_INVALID_ARGUMENT,
+  /**
+   * The seat number is past the last seat
+   */
+  BP_STATUS_NO_SEAT,
+  BP_STATUS_GAME_OVER,
+  /**
+   * Only the player whose turn it is can move
+   */
+  BP_STATUS_NOT_YOUR_TURN,
+  /**
+   * A move has to be made before drawing
+   */
+  BP_STATUS_MOVE_FIRST,
+  /**
+   * The player has moved and has to draw next
+   */
+  BP_STATUS_DRAW_NEXT,
+  BP_STATUS_CUSTOM,
+  BP_STATUS_SAME_CONTINENT,
+  BP_STATUS_GREY_HELD,
+  BP_STATUS_INVALID_BONUS,
+  BP_STATUS_NO_TOP_COUNTRY,
+  BP_STATUS_UNPLAYABLE,
+  BP_STATUS_INVALID_INDEX,
+  BP_STATUS_NOT_GREY,
+  BP_STATUS_NO_PLAYERS,
+  BP_STATUS_NOT_ENOUGH_CARDS,
+} BpStatus;
+
+/**
+ * A game in progress, created with `bp_game_new` and freed with `bp_game_free`
+ */
+typedef struct BpGame BpGame;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Deal a game for `players` seats, with `seed` deciding the deal, and put it in `game`
+ *
+ * # Safety
+ *
+ * `game` must be null or point somewhere a pointer can be written.
+ */
+enum BpStatus bp_game_new(size_t players, uint64_t seed, struct BpGame **game);
+
+/**
+ * Free a game made by `bp_game_new`. Does nothing with null.
+ *
+ * # Safety
+ *
+ * `game` must be null or a pointer from `bp_game_new` that hasn't been freed.
+ */
+void bp_game_free(struct BpGame *game);
+
+/**
+ * Whose turn it is, counting seats from 0, or `SIZE_MAX` with no game
+ *
+ * # Safety
+ *
+ * `game` must be null or a live pointer from `bp_game_new`.
+ */
+size_t bp_game_turn(const struct BpGame *game);
+
+/**
+ * Whether the game has finished, which a missing game counts as so loops over it stop
+ *
+ * # Safety
+ *
+ * `game` must be null or a live pointer from `bp_game_new`.
+ */
+bool bp_game_is_over(const struct BpGame *game);
+
+/**
+ * Put everything `seat` can see in `json`, including its own hand but nobody else's
+ *
+ * # Safety
+ *
+ * `game` must be null or a live pointer from `bp_game_new`, and `json` null or somewhere
+ * a pointer can be written.
+ */
+enum BpStatus bp_game_view(const struct BpGame *game, size_t seat, char **json);
+
+/**
+ * Put what `seat` can do now in `json`, as `{"phase": "move", "legal_moves": [..]}`
+ *
+ * # Safety
+ *
+ * As for `bp_game_view`.
+ */
+enum BpStatus bp_game_legal_moves(const struct BpGame *game, size_t seat, char **json);
+
+/**
+ * Put the events after the first `since` in `json`, as
+ * `{"events": [..], "next": 3, "over": false}`
+ *
+ * # Safety
+ *
+ * As for `bp_game_view`.
+ */
+enum BpStatus bp_game_events(const struct BpGame *game, size_t since, char **json);
+
+/**
+ * Play a move like `{"command": "play", "index": 0}` for `seat`, or
+ * `{"command": "draw", "from": "deck"}` once it has moved
+ *
+ * # Safety
+ *
+ * `game` must be null or a live pointer from `bp_game_new`, and `command` null or a
+ * nul-terminated string.
+ */
+enum BpStatus bp_game_apply_move(struct BpGame *game, size_t seat, const char *command);
+
+/**
+ * Free a string returned by this library. Does nothing with null.
+ *
+ * # Safety
+ *
+ * `string` must be null or a string from this library that hasn't been freed.
+ */
+void bp_string_free(char *string);
+
+/**
+ * A description of the latest failure on this thread, or an empty string. Stays valid
+ * until the next failing call on the same thread.
+ */
+const char *bp_last_error(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* BACKPACKER_H */
@@ -0,0 +1,125 @@
+use std::{error::Error, fmt};
+
+use crate::{Board, Card, Country, Player};
+
+/// The difference between the cards in a game and the deck it was dealt from
+#[derive(Debug)]
+pub struct CardLeak {
+    // In the deck but nowhere in the game
+    missing: Vec<Card>,
+    // In the game but not in the deck (or in it too many times)
+    extra: Vec<Card>,
+}
+
+impl Error for CardLeak {}
+
+impl fmt::Display for CardLeak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "card conservation violated")?;
+        for card in &self.missing {
+            writeln!(f, "  - {}", card)?;
+        }
+        for card in &self.extra {
+            writeln!(f, "  + {}", card)?;
+        }
+        Ok(())
+    }
+}
+
+impl Player {
+    /// Every card this player holds, has played or has in play.
+    /// Attached bonuses are split back out into their own cards.
+    fn all_cards(&self) -> Vec<Card> {
+        let mut cards: Vec<Card> = self.hand.clone();
+
+        for country in &self.pile {
+            cards.extend(country.bonus.iter().map(|bonus| Card::Bonus(*bonus)));
+            cards.push(Card::Country(Country {
+                bonus: Vec::new(),
+                ..country.clone()
+            }));
+        }
+
+        cards.extend(
+            self.status
+                .hazards
+                .iter()
+                .map(|hazard| Card::Grey(hazard.card)),
+        );
+        cards
+    }
+}
+
+impl Board {
+    /// Every card currently in the game, wherever it is
+    fn all_cards(&self) -> Vec<Card> {
+        let mut cards: Vec<Card> = self.future.clone();
+        cards.extend(self.past.iter().cloned());
+        for player in &self.players {
+            cards.extend(player.all_cards());
+        }
+        cards
+    }
+
+    /// Check that the cards across the deck, discard pile, hands, piles and
+    /// in-play hazards are exactly the cards of the deck the game was dealt from
+    pub fn audit(&self) -> Result<(), CardLeak> {
+        let mut expected = self.deck.cards().to_vec();
+        let mut actual = self.all_cards();
+        expected.sort();
+        actual.sort();
+
+        let mut missing = Vec::new();
+        let mut extra = Vec::new();
+
+        let mut expected = expected.into_iter().peekable();
+        let mut actual = actual.into_iter().peekable();
+        loop {
+            match (expected.peek(), actual.peek()) {
+                (None, None) => break,
+                (Some(_), None) => missing.push(expected.next().unwrap()),
+                (None, Some(_)) => extra.push(actual.next().unwrap()),
+                (Some(e), Some(a)) => match e.cmp(a) {
+                    std::cmp::Ordering::Less => missing.push(expected.next().unwrap()),
+                    std::cmp::Ordering::Greater => extra.push(actual.next().unwrap()),
+                    std::cmp::Ordering::Equal => {
+                        expected.next();
+                        actual.next();
+                    }
+                },
+            }
+        }
+
+        if missing.is_empty() && extra.is_empty() {
+            Ok(())
+        } else {
+            Err(CardLeak { missing, extra })
+        }
+    }
+
+    /// Panic with the difference if cards have leaked, when debug auditing is on
+    pub(crate) fn check_cards(&self) {
+        if !self.debug_audit {
+            return;
+        }
+        if let Err(leak) = self.audit() {
+            panic!("{}", leak);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Move, Rules};
+
+    #[test]
+    #[should_panic(expected = "card conservation violated")]
+    fn a_lost_card_is_caught_by_the_next_move() {
+        let mut board = Board::new_seeded_game(2, Rules::default(), 1).unwrap();
+        board.set_debug_audit(true);
+        assert!(board.begin_turn());
+
+        board.future.pop();
+        let _ = board.apply_move(Move::Discard(0));
+    }
+}
@@ -0,0 +1,183 @@
+use rand::{prelude::*, rngs::StdRng};
+
+use crate::{Board, Card, Move, Player};
+
+/// Something that can pick moves for a seat without any input from a person.
+/// Deriving `Clone` is enough to implement the `StrategyClone` it needs.
+pub trait Strategy: Send + StrategyClone {
+    /// Pick one of `moves`, which is never empty and only contains legal moves
+    fn choose_move(&mut self, board: &Board, moves: &[Move]) -> Move;
+
+    /// Whether to take the top discard rather than drawing blind, when the rules allow it
+    fn take_discard(&mut self, _board: &Board) -> bool {
+        false
+    }
+}
+
+/// Copies a boxed strategy along with whatever it remembers, so games with bots can be cloned
+pub trait StrategyClone {
+    fn clone_box(&self) -> Box<dyn Strategy>;
+}
+
+impl<T: Strategy + Clone + 'static> StrategyClone for T {
+    fn clone_box(&self) -> Box<dyn Strategy> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Strategy> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+type Constructor = fn(u64) -> Box<dyn Strategy>;
+
+// Every strategy that can be picked by name
+const REGISTRY: &[(&str, Constructor)] = &[
+    ("random", |seed| Box::new(RandomBot::new(seed))),
+    ("greedy", |_| Box::new(BankerBot::new(24))),
+    ("cautious", |_| Box::new(BankerBot::new(10))),
+];
+
+/// Names of all registered strategies
+pub fn strategy_names() -> Vec<&'static str> {
+    REGISTRY.iter().map(|(name, _)| *name).collect()
+}
+
+/// Build a registered strategy by name. `seed` drives any randomness it uses.
+pub fn strategy(name: &str, seed: u64) -> Option<Box<dyn Strategy>> {
+    REGISTRY
+        .iter()
+        .find(|(registered, _)| *registered == name)
+        .map(|(_, constructor)| constructor(seed))
+}
+
+/// Picks any legal move at random
+#[derive(Clone)]
+struct RandomBot {
+    rng: StdRng,
+}
+
+impl RandomBot {
+    fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for RandomBot {
+    fn choose_move(&mut self, _board: &Board, moves: &[Move]) -> Move {
+        *moves.choose(&mut self.rng).unwrap()
+    }
+
+    fn take_discard(&mut self, _board: &Board) -> bool {
+        self.rng.random()
+    }
+}
+
+/// Builds up a pile as fast as it can and goes home once it's worth `threshold` points
+#[derive(Clone)]
+struct BankerBot {
+    threshold: u32,
+}
+
+impl BankerBot {
+    fn new(threshold: u32) -> Self {
+        Self { threshold }
+    }
+
+    /// Points a card would add to the pile if played now
+    fn value(player: &Player, card: &Card) -> u32 {
+        match card {
+            Card::Country(country) => country.score as u32,
+            Card::Bonus(_) => player.top_country().map_or(0, |top| top.score as u32),
+            _ => 0,
+        }
+    }
+}
+
+impl Strategy for BankerBot {
+    fn choose_move(&mut self, board: &Board, moves: &[Move]) -> Move {
+        let player = &board.players[board.turn];
+
+        // Bank the pile if it's big enough, or if the deck is about to run out
+        let last_chance = board.future.len() <= board.players.len() * 2;
+        let trip_score = player.trip_score();
+        if moves.contains(&Move::GoHome) && (trip_score >= self.threshold || last_chance) {
+            return Move::GoHome;
+        }
+
+        // Play whatever adds the most to the pile
+        let best_play = moves
+            .iter()
+            .filter_map(|mv| match mv {
+                Move::Play(i) => Some((*i, Self::value(player, &player.hand[*i]))),
+                _ => None,
+            })
+            .max_by_key(|(_, value)| *value);
+        if let Some((i, value)) = best_play
+            && value > 0
+        {
+            return Move::Play(i);
+        }
+
+        // Get grey cards out of the way so they don't block going home later
+        if let Some(i) = player
+            .hand
+            .iter()
+            .position(|card| matches!(card, Card::Grey(_)))
+            && moves.contains(&Move::Play(i))
+        {
+            return Move::Play(i);
+        }
+
+        // Otherwise throw away the least useful card
+        moves
+            .iter()
+            .filter(|mv| matches!(mv, Move::Discard(_)))
+            .min_by_key(|mv| match mv {
+                Move::Discard(i) => Self::value(player, &player.hand[*i]),
+                _ => u32::MAX,
+            })
+            .copied()
+            .unwrap_or(moves[0])
+    }
+
+    fn take_discard(&mut self, board: &Board) -> bool {
+        let player = &board.players[board.turn];
+        match board.top_discard() {
+            Some(Card::Country(country)) => player.can_play_country(country).is_ok(),
+            Some(Card::Bonus(bonus)) => player.can_play_bonus(bonus).is_ok(),
+            _ => false,
+        }
+    }
+}
+
+impl Board {
+    /// Play the current player's turn with a bot, printing nothing
+    pub fn bot_turn(&mut self, bot: &mut dyn Strategy) {
+        if !self.begin_turn() {
+            return;
+        }
+
+        let moves = self.legal_moves();
+        let mv = bot.choose_move(self, &moves);
+        self.apply_move(mv)
+            .expect("Strategies should only pick legal moves");
+
+        let from_discard = self.can_take_discard() && bot.take_discard(self);
+        self.end_turn(from_discard);
+    }
+
+    /// Play the rest of the game with a bot in every seat, printing nothing
+    pub fn bot_game(&mut self, bots: &mut [Box<dyn Strategy>]) {
+        assert_eq!(bots.len(), self.players.len(), "Need one bot per seat");
+
+        while !self.is_over() {
+            let seat = self.turn;
+            self.bot_turn(bots[seat].as_mut());
+        }
+    }
+}
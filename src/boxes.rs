@@ -0,0 +1,305 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::{Board, Bonus, Card, Country, locale::trf, style::Theme};
+
+/// How cards are drawn in the turn display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardStyle {
+    /// One coloured name per card
+    Compact,
+    /// A box per card drawn with Unicode box-drawing characters
+    Boxed,
+    /// A box per card drawn with plain ASCII
+    Ascii,
+}
+
+static CARD_STYLE: AtomicU8 = AtomicU8::new(CardStyle::Compact as u8);
+
+impl CardStyle {
+    pub fn current() -> Self {
+        match CARD_STYLE.load(Ordering::Relaxed) {
+            1 => Self::Boxed,
+            2 => Self::Ascii,
+            _ => Self::Compact,
+        }
+    }
+
+    /// Draw cards in this style from now on
+    pub fn set(self) {
+        CARD_STYLE.store(self as u8, Ordering::Relaxed);
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "compact" => Some(Self::Compact),
+            "boxed" => Some(Self::Boxed),
+            "ascii" => Some(Self::Ascii),
+            _ => None,
+        }
+    }
+
+    fn charset(self) -> &'static Charset {
+        match self {
+            Self::Ascii => &ASCII,
+            _ => &UNICODE,
+        }
+    }
+}
+
+struct Charset {
+    top_left: char,
+    top_right: char,
+    bottom_left: char,
+    bottom_right: char,
+    left_join: char,
+    right_join: char,
+    horizontal: char,
+    vertical: char,
+    attached: &'static str,
+}
+
+const UNICODE: Charset = Charset {
+    top_left: '┌',
+    top_right: '┐',
+    bottom_left: '└',
+    bottom_right: '┘',
+    left_join: '├',
+    right_join: '┤',
+    horizontal: '─',
+    vertical: '│',
+    attached: "↳",
+};
+
+const ASCII: Charset = Charset {
+    top_left: '+',
+    top_right: '+',
+    bottom_left: '+',
+    bottom_right: '+',
+    left_join: '+',
+    right_join: '+',
+    horizontal: '-',
+    vertical: '|',
+    attached: "+",
+};
+
+/// Width of `text` on screen, skipping any colour escape codes
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip to the end of the escape sequence
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+fn pad(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(visible_width(text));
+    format!("{}{}", text, " ".repeat(padding))
+}
+
+impl Bonus {
+    fn icon(&self, style: CardStyle) -> &'static str {
+        match (style, self) {
+            (CardStyle::Ascii, Bonus::Beach) => "B",
+            (CardStyle::Ascii, Bonus::Culture) => "C",
+            (CardStyle::Ascii, Bonus::Trekking) => "T",
+            (CardStyle::Ascii, Bonus::Wildlife) => "W",
+            (_, Bonus::Beach) => "≈",
+            (_, Bonus::Culture) => "♜",
+            (_, Bonus::Trekking) => "▲",
+            (_, Bonus::Wildlife) => "♣",
+        }
+    }
+}
+
+impl Country {
+    /// Icons for every bonus this country can take
+    fn bonus_icons(&self, style: CardStyle) -> String {
+        let icons: Vec<&str> = self
+            .allowed_bonus
+            .chars()
+            .map(|c| Bonus::parse(&c).icon(style))
+            .collect();
+        Theme::current().bonus.paint(&icons.join(" ")).to_string()
+    }
+}
+
+/// The lines inside a card's box, before the border is drawn
+fn card_lines(card: &Card, style: CardStyle) -> Vec<String> {
+    let charset = style.charset();
+
+    match card {
+        Card::Country(country) => {
+            let mut lines = vec![
+                country.to_string(),
+                format!("{} {}", country.continent(), country.score),
+                country.bonus_icons(style),
+            ];
+            for bonus in &country.bonus {
+                lines.push(format!("{} {}", charset.attached, bonus));
+            }
+            lines
+        }
+        Card::Bonus(bonus) => vec![format!("{} {}", bonus.icon(style), bonus)],
+        _ => vec![card.to_string()],
+    }
+}
+
+/// A card drawn as a box, one string per line, all the same width
+fn card_box(card: &Card, label: Option<&str>, style: CardStyle) -> Vec<String> {
+    let charset = style.charset();
+    let lines = card_lines(card, style);
+    let label = label.map(|label| format!(" {} ", label)).unwrap_or_default();
+    let inner = lines
+        .iter()
+        .map(|line| visible_width(line))
+        .chain([visible_width(&label)])
+        .max()
+        .unwrap_or(0)
+        + 2;
+
+    let horizontal = |width: usize| charset.horizontal.to_string().repeat(width);
+
+    let mut drawn = vec![format!(
+        "{}{}{}{}",
+        charset.top_left,
+        label,
+        horizontal(inner - visible_width(&label)),
+        charset.top_right
+    )];
+    for line in lines {
+        drawn.push(format!(
+            "{} {} {}",
+            charset.vertical,
+            pad(&line, inner - 2),
+            charset.vertical
+        ));
+    }
+    drawn.push(format!(
+        "{}{}{}",
+        charset.bottom_left,
+        horizontal(inner),
+        charset.bottom_right
+    ));
+    drawn
+}
+
+/// Stack boxes on top of each other, sharing the border between them
+fn stack(boxes: Vec<Vec<String>>, style: CardStyle) -> Vec<String> {
+    let charset = style.charset();
+    let width = boxes
+        .iter()
+        .flat_map(|lines| lines.iter().map(|line| visible_width(line)))
+        .max()
+        .unwrap_or(0);
+
+    let mut stacked = Vec::new();
+    let count = boxes.len();
+    for (i, lines) in boxes.into_iter().enumerate() {
+        let last = lines.len() - 1;
+        for (j, line) in lines.into_iter().enumerate() {
+            if j == 0 && i != 0 {
+                // This box's top border is drawn by the bottom of the box above
+                continue;
+            }
+            let line = if j == last && i + 1 != count {
+                format!(
+                    "{}{}{}",
+                    charset.left_join,
+                    charset.horizontal.to_string().repeat(width - 2),
+                    charset.right_join
+                )
+            } else if j == 0 || j == last {
+                stretch_border(&line, width, charset)
+            } else {
+                let inner = line
+                    .strip_suffix(charset.vertical)
+                    .unwrap_or(&line)
+                    .to_string();
+                format!("{}{}", pad(&inner, width - 1), charset.vertical)
+            };
+            stacked.push(line);
+        }
+    }
+    stacked
+}
+
+/// Widen a top or bottom border to `width` by repeating its horizontal line
+fn stretch_border(line: &str, width: usize, charset: &Charset) -> String {
+    let mut chars: Vec<char> = line.chars().collect();
+    let corner = chars.pop().unwrap_or(charset.horizontal);
+    let extra = width.saturating_sub(visible_width(line));
+    chars.extend(std::iter::repeat_n(charset.horizontal, extra));
+    chars.push(corner);
+    chars.into_iter().collect()
+}
+
+/// Lay columns of lines out next to each other
+fn side_by_side(columns: Vec<Vec<String>>) -> Vec<String> {
+    let height = columns.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = columns
+        .iter()
+        .map(|lines| lines.iter().map(|line| visible_width(line)).max().unwrap_or(0))
+        .collect();
+
+    (0..height)
+        .map(|row| {
+            columns
+                .iter()
+                .zip(&widths)
+                .map(|(lines, width)| pad(lines.get(row).map_or("", String::as_str), *width))
+                .collect::<Vec<String>>()
+                .join(" ")
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+impl Board {
+    /// The current player's hand as a row of boxes, each labelled with its number
+    pub(crate) fn show_boxed_hand(&self, style: CardStyle) {
+        let boxes = self.players[self.turn]
+            .hand
+            .iter()
+            .enumerate()
+            .map(|(i, card)| card_box(card, Some(&(i + 1).to_string()), style))
+            .collect();
+
+        for line in side_by_side(boxes) {
+            println!("{}", line);
+        }
+    }
+
+    /// Every player's pile as a stack of boxes, the piles next to each other
+    pub(crate) fn show_boxed_piles(&self, style: CardStyle) {
+        let piles: Vec<Vec<String>> = self
+            .players
+            .iter()
+            .filter(|player| !player.pile.is_empty())
+            .map(|player| {
+                let boxes = player
+                    .pile
+                    .iter()
+                    .map(|country| card_box(&Card::Country(country.clone()), None, style))
+                    .collect();
+                let mut column = vec![trf("heading.pile", &[&player.name, &player.trip_score()])];
+                column.extend(stack(boxes, style));
+                column
+            })
+            .collect();
+
+        for line in side_by_side(piles) {
+            println!("{}", line);
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use crate::{
+    AdviceType, Bonus, Card, GreyType, Move, Special,
+    locale::{tr, trf},
+};
+
+/// What the current player typed at the turn prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Command {
+    Move(Move),
+    /// The `0` shortcut, which asks for the card to discard separately
+    PickDiscard,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verb {
+    Play,
+    Bonus,
+    Grey,
+    Discard,
+    Home,
+}
+
+// Each verb's English word, always understood, and its catalogue key for the current locale
+const VERBS: &[(Verb, &str, &str)] = &[
+    (Verb::Play, "play", "command.play"),
+    (Verb::Bonus, "bonus", "command.bonus"),
+    (Verb::Grey, "grey", "command.grey"),
+    (Verb::Discard, "discard", "command.discard"),
+    (Verb::Home, "home", "command.home"),
+];
+
+/// Which cards a card reference may pick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    Any,
+    Bonus,
+    Grey,
+}
+
+impl Kind {
+    fn matches(self, card: &Card) -> bool {
+        match self {
+            Kind::Any => true,
+            Kind::Bonus => matches!(card, Card::Bonus(_)),
+            Kind::Grey => matches!(card, Card::Grey(_)),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Any => tr("command.kind.card"),
+            Kind::Bonus => tr("command.kind.bonus"),
+            Kind::Grey => tr("command.kind.grey"),
+        }
+    }
+}
+
+/// Lowercase `text` and drop accents, so `peru` matches `Perú`
+fn fold(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'ä' | 'â' => 'a',
+            'é' | 'è' | 'ë' | 'ê' => 'e',
+            'í' | 'ì' | 'ï' | 'î' => 'i',
+            'ó' | 'ò' | 'ö' | 'ô' => 'o',
+            'ú' | 'ù' | 'ü' | 'û' => 'u',
+            'ñ' => 'n',
+            _ => c,
+        })
+        .collect()
+}
+
+/// Every name a card can be referred to by, in English and the current locale
+fn names(card: &Card) -> Vec<String> {
+    let names = match card {
+        Card::Country(country) => vec![country.name.clone(), country.local_name().to_string()],
+        Card::Bonus(bonus) => {
+            let (word, key) = match bonus {
+                Bonus::Beach => ("beach", "bonus.beach"),
+                Bonus::Culture => ("culture", "bonus.culture"),
+                Bonus::Trekking => ("trekking", "bonus.trekking"),
+                Bonus::Wildlife => ("wildlife", "bonus.wildlife"),
+            };
+            vec![word.to_string(), tr(key).to_string()]
+        }
+        Card::Advice(advice) if advice.variant == AdviceType::Money => {
+            vec!["money".to_string(), tr("advice.money").to_string()]
+        }
+        Card::Advice(advice) => {
+            let (pre, name) = advice.keys();
+            let good = if advice.good { "good" } else { "bad" };
+            let variant = name.trim_start_matches("advice.");
+            vec![
+                format!("{} {}", good, variant),
+                trf("advice.format", &[&tr(pre), &tr(name)]),
+            ]
+        }
+        Card::Grey(GreyType::MissedFlight) => {
+            vec![
+                "missed flight".to_string(),
+                tr("grey.missed_flight").to_string(),
+            ]
+        }
+        Card::Special(Special::CerditCard) => {
+            vec![
+                "credit card".to_string(),
+                tr("special.credit_card").to_string(),
+            ]
+        }
+    };
+
+    names.iter().map(|name| fold(name)).collect()
+}
+
+/// Find the card in `hand` that `reference` means, by 1-based number, full name, or the
+/// start of any word in its name
+pub(crate) fn find_card(reference: &str, hand: &[Card], kind: Kind) -> Result<usize, String> {
+    let reference = fold(reference);
+
+    if let Ok(number) = reference.parse::<usize>() {
+        let card = number
+            .checked_sub(1)
+            .and_then(|i| hand.get(i))
+            .ok_or_else(|| trf("command.no_number", &[&number]))?;
+        if !kind.matches(card) {
+            return Err(trf("command.not_kind", &[card, &kind.name()]));
+        }
+        return Ok(number - 1);
+    }
+
+    let of_kind: Vec<usize> = (0..hand.len())
+        .filter(|&i| kind.matches(&hand[i]))
+        .collect();
+    if of_kind.is_empty() {
+        return Err(trf("command.none_of_kind", &[&kind.name()]));
+    }
+
+    let candidates: Vec<usize> = if reference.is_empty() {
+        if kind == Kind::Any {
+            return Err(tr("command.which").to_string());
+        }
+        of_kind
+    } else {
+        let exact: Vec<usize> = of_kind
+            .iter()
+            .copied()
+            .filter(|&i| names(&hand[i]).contains(&reference))
+            .collect();
+        if exact.is_empty() {
+            of_kind
+                .into_iter()
+                .filter(|&i| {
+                    names(&hand[i]).iter().any(|name| {
+                        name.starts_with(&reference)
+                            || name
+                                .split_whitespace()
+                                .any(|word| word.starts_with(&reference))
+                    })
+                })
+                .collect()
+        } else {
+            exact
+        }
+    };
+
+    // Identical cards are interchangeable, so only different cards make a reference ambiguous
+    let mut distinct: Vec<usize> = Vec::new();
+    for i in candidates {
+        if !distinct.iter().any(|&j| hand[j] == hand[i]) {
+            distinct.push(i);
+        }
+    }
+
+    match distinct[..] {
+        [] => Err(trf("command.unknown_card", &[&reference])),
+        [i] => Ok(i),
+        _ => {
+            let options: Vec<String> = distinct.iter().map(|&i| hand[i].to_string()).collect();
+            Err(trf("command.ambiguous", &[&reference, &options.join(", ")]))
+        }
+    }
+}
+
+/// Parse what the current player typed at the turn prompt, given their hand.
+///
+/// `play <card>`, `bonus [type]`, `grey [card]`, `discard <card>` and `home` are understood
+/// in English and the current locale, and a card named on its own is played. The old
+/// number shortcuts still work: a card's number plays it, `0` asks which card to discard
+/// and `10` goes home.
+pub(crate) fn parse_command(input: &str, hand: &[Card]) -> Result<Command, String> {
+    let input = fold(input);
+
+    if let Ok(number) = input.parse::<usize>() {
+        return match number {
+            0 => Ok(Command::PickDiscard),
+            10 if hand.len() < 10 => Ok(Command::Move(Move::GoHome)),
+            _ => find_card(&input, hand, Kind::Any).map(|i| Command::Move(Move::Play(i))),
+        };
+    }
+
+    let (word, rest) = input.split_once(' ').unwrap_or((&input, ""));
+    let verb = VERBS
+        .iter()
+        .find(|(_, english, key)| word == *english || word == fold(tr(key)))
+        .map(|(verb, _, _)| *verb);
+
+    let mv = match verb {
+        Some(Verb::Home) => Move::GoHome,
+        Some(Verb::Play) => Move::Play(find_card(rest, hand, Kind::Any)?),
+        Some(Verb::Bonus) => Move::Play(find_card(rest, hand, Kind::Bonus)?),
+        Some(Verb::Grey) => Move::Play(find_card(rest, hand, Kind::Grey)?),
+        Some(Verb::Discard) => Move::Discard(find_card(rest, hand, Kind::Any)?),
+        None if input == "go home" => Move::GoHome,
+        None => Move::Play(find_card(&input, hand, Kind::Any)?),
+    };
+
+    Ok(Command::Move(mv))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Country;
+
+    fn hand() -> Vec<Card> {
+        vec![
+            Card::Country(Country::new("Mali", 3, "b")),
+            Card::Country(Country::new("Mexico", 4, "c")),
+            Card::Country(Country::new("Peru", 2, "t")),
+            Card::Bonus(Bonus::Beach),
+            Card::Grey(GreyType::MissedFlight),
+        ]
+    }
+
+    #[test]
+    fn a_unique_prefix_picks_its_card() {
+        assert_eq!(
+            parse_command("play mex", &hand()),
+            Ok(Command::Move(Move::Play(1)))
+        );
+        assert_eq!(
+            parse_command("discard pe", &hand()),
+            Ok(Command::Move(Move::Discard(2)))
+        );
+        assert_eq!(find_card("fli", &hand(), Kind::Grey), Ok(4));
+    }
+
+    #[test]
+    fn an_ambiguous_prefix_lists_the_cards_it_could_be() {
+        let error = parse_command("play m", &hand()).unwrap_err();
+        for name in ["Mali", "Mexico", "Missed Flight"] {
+            assert!(error.contains(name), "{} isn't in `{}`", name, error);
+        }
+        assert!(!error.contains("Peru"));
+    }
+
+    #[test]
+    fn identical_cards_are_not_ambiguous() {
+        let mut hand = hand();
+        hand.push(Card::Bonus(Bonus::Beach));
+        assert_eq!(find_card("bea", &hand, Kind::Bonus), Ok(3));
+    }
+
+    #[test]
+    fn a_number_past_the_hand_is_refused() {
+        assert!(parse_command("7", &hand()).is_err());
+        assert!(parse_command("discard 6", &hand()).is_err());
+        assert!(find_card("0", &hand(), Kind::Any).is_err());
+        assert_eq!(
+            parse_command("5", &hand()),
+            Ok(Command::Move(Move::Play(4)))
+        );
+    }
+
+    #[test]
+    fn a_bare_card_name_is_played() {
+        assert_eq!(
+            parse_command("Peru", &hand()),
+            Ok(Command::Move(Move::Play(2)))
+        );
+        assert_eq!(
+            parse_command("beach", &hand()),
+            Ok(Command::Move(Move::Play(3)))
+        );
+    }
+
+    #[test]
+    fn number_shortcuts_still_work() {
+        assert_eq!(parse_command("0", &hand()), Ok(Command::PickDiscard));
+        assert_eq!(
+            parse_command("10", &hand()),
+            Ok(Command::Move(Move::GoHome))
+        );
+    }
+}
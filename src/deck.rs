@@ -0,0 +1,421 @@
+use std::{error::Error, fmt, fs, path::Path, str::FromStr};
+
+use crate::{Advice, AdviceType, Bonus, Card, Country, GreyType, HAND_SIZE, Special};
+
+/// The cards a game is dealt from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deck {
+    cards: Vec<Card>,
+}
+
+impl Deck {
+    /// The deck the game comes with
+    pub fn standard() -> Self {
+        Self {
+            cards: Card::deck(),
+        }
+    }
+
+    /// Read a deck file, reporting every line that can't be understood.
+    ///
+    /// Each line is one card, optionally starting with a count like `3x`:
+    ///
+    /// ```text
+    /// country South Africa 4 bcw
+    /// 3x bonus beach
+    /// advice good money
+    /// advice bad timing
+    /// grey missed_flight
+    /// special credit_card
+    /// ```
+    ///
+    /// A country's allowed bonuses are letters from `b`, `c`, `t` and `w`, or `-` for none.
+    /// Other letters are kept for `Deck::validate` to report. Lines starting with `#` are
+    /// comments.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let with_path = |e: String| {
+            let lines: Vec<String> = e
+                .lines()
+                .map(|line| format!("{}: {}", path.display(), line))
+                .collect();
+            lines.join("\n")
+        };
+        let contents = fs::read_to_string(path).map_err(|e| with_path(e.to_string()))?;
+        contents.parse().map_err(with_path)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    pub(crate) fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// Check the deck for mistakes that would otherwise go unnoticed, or only show up part
+    /// way through a game of `players`, reporting every one found
+    pub fn validate(&self, players: usize) -> Result<(), DeckProblems> {
+        let countries: Vec<&Country> = self.cards.iter().filter_map(Card::country).collect();
+        let mut problems = Vec::new();
+
+        for (i, country) in countries.iter().enumerate() {
+            let name = || country.name.clone();
+            let earlier = &countries[..i];
+
+            // Problems with the name are reported once, at the first copy
+            if !earlier.contains(country) {
+                if country.known_continent().is_none() {
+                    problems.push(DeckProblem::UnknownContinent { country: name() });
+                }
+                let copies = countries.iter().filter(|other| *other == country).count();
+                if copies > 1 {
+                    problems.push(DeckProblem::DuplicateCountry {
+                        country: name(),
+                        copies,
+                    });
+                }
+            }
+            // and problems with the bonuses once for each different set of them
+            if earlier.iter().any(|other| {
+                other.name == country.name && other.allowed_bonus == country.allowed_bonus
+            }) {
+                continue;
+            }
+            for letter in country.allowed_bonus.chars() {
+                if !Bonus::ALL.iter().any(|bonus| bonus.unparse() == letter) {
+                    problems.push(DeckProblem::InvalidBonusLetter {
+                        country: name(),
+                        letter,
+                    });
+                }
+            }
+            if country.allowed_bonus.is_empty() {
+                problems.push(DeckProblem::NoBonuses { country: name() });
+            }
+        }
+
+        for bonus in Bonus::ALL {
+            let in_deck = self.cards.contains(&Card::Bonus(bonus));
+            let allowed = countries
+                .iter()
+                .any(|country| country.allowed_bonus.contains(bonus.unparse()));
+            if in_deck && !allowed {
+                problems.push(DeckProblem::UnplayableBonus {
+                    bonus: card_line(&Card::Bonus(bonus)),
+                });
+            }
+        }
+
+        // Everyone's hand has to be dealt with at least one card left to draw
+        if HAND_SIZE
+            .checked_mul(players)
+            .is_none_or(|held| held >= self.len())
+        {
+            problems.push(DeckProblem::NotEnoughCards {
+                players,
+                cards: self.len(),
+            });
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(DeckProblems { problems })
+        }
+    }
+}
+
+/// One mistake in a deck
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeckProblem {
+    /// A country name that isn't on any continent, which would count as Antarctica
+    UnknownContinent {
+        country: String,
+    },
+    DuplicateCountry {
+        country: String,
+        copies: usize,
+    },
+    /// A letter in a country's allowed bonuses that isn't `b`, `c`, `t` or `w`
+    InvalidBonusLetter {
+        country: String,
+        letter: char,
+    },
+    /// A bonus card in the deck that no country allows
+    UnplayableBonus {
+        bonus: String,
+    },
+    /// A country no bonus can be played on
+    NoBonuses {
+        country: String,
+    },
+    NotEnoughCards {
+        players: usize,
+        cards: usize,
+    },
+}
+
+impl DeckProblem {
+    /// Whether a game can't be played with the deck at all, rather than the deck being
+    /// playable but probably not what was meant
+    pub fn is_fatal(&self) -> bool {
+        matches!(
+            self,
+            Self::InvalidBonusLetter { .. } | Self::NotEnoughCards { .. }
+        )
+    }
+}
+
+impl fmt::Display for DeckProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownContinent { country } => write!(
+                f,
+                "`{}` isn't on any continent, so it would count as Antarctica",
+                country
+            ),
+            Self::DuplicateCountry { country, copies } => {
+                write!(f, "`{}` is in the deck {} times", country, copies)
+            }
+            Self::InvalidBonusLetter { country, letter } => write!(
+                f,
+                "`{}` allows bonus `{}`, which isn't one of b, c, t or w",
+                country, letter
+            ),
+            Self::UnplayableBonus { bonus } => {
+                write!(f, "no country allows `{}`, so it can't be played", bonus)
+            }
+            Self::NoBonuses { country } => write!(f, "`{}` allows no bonuses", country),
+            Self::NotEnoughCards { players, cards } => write!(
+                f,
+                "{} cards aren't enough to deal {} players a hand and leave some to draw",
+                cards, players
+            ),
+        }
+    }
+}
+
+/// Everything `Deck::validate` found wrong with a deck
+#[derive(Debug)]
+pub struct DeckProblems {
+    pub problems: Vec<DeckProblem>,
+}
+
+impl Error for DeckProblems {}
+
+impl fmt::Display for DeckProblems {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.problems.len() {
+            1 => write!(f, "the deck has a problem")?,
+            count => write!(f, "the deck has {} problems", count)?,
+        }
+        for problem in &self.problems {
+            write!(f, "\n  - {}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_card(line: &str) -> Result<Card, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+
+    let card = match words[..] {
+        ["country", ref rest @ ..] if rest.len() >= 3 => {
+            let (name, rest) = rest.split_at(rest.len() - 2);
+            let score = rest[0]
+                .parse()
+                .map_err(|_| format!("invalid score `{}`", rest[0]))?;
+            let allowed_bonus = if rest[1] == "-" { "" } else { rest[1] };
+            Card::Country(Country::new(&name.join(" "), score, allowed_bonus))
+        }
+        ["country", ..] => return Err("expected `country <name> <score> <bonuses>`".into()),
+        ["bonus", bonus] => Card::Bonus(match bonus {
+            "beach" => Bonus::Beach,
+            "culture" => Bonus::Culture,
+            "trekking" => Bonus::Trekking,
+            "wildlife" => Bonus::Wildlife,
+            _ => return Err(format!("unknown bonus `{}`", bonus)),
+        }),
+        ["advice", good, variant] => {
+            let good = match good {
+                "good" => true,
+                "bad" => false,
+                _ => return Err(format!("advice is `good` or `bad`, not `{}`", good)),
+            };
+            let variant = match variant {
+                "money" => AdviceType::Money,
+                "bureaucracy" => AdviceType::Bureaucracy,
+                "timing" => AdviceType::Timing,
+                "transport" => AdviceType::Transport,
+                _ => return Err(format!("unknown advice `{}`", variant)),
+            };
+            Card::Advice(Advice::new(good, variant))
+        }
+        ["grey", "missed_flight"] => Card::Grey(GreyType::MissedFlight),
+        ["special", "credit_card"] => Card::Special(Special::CerditCard),
+        _ => return Err(format!("unknown card `{}`", line)),
+    };
+
+    Ok(card)
+}
+
+impl FromStr for Deck {
+    type Err = String;
+
+    /// Reads the deck file format described on `Deck::load`, with one line in the error for
+    /// each line that can't be understood
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        let mut cards = Vec::new();
+        let mut errors = Vec::new();
+
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (count, card) = match line.split_once(' ') {
+                Some((count, card)) if count.ends_with('x') => {
+                    match count[..count.len() - 1].parse::<usize>() {
+                        Ok(count) => (count, card),
+                        Err(_) => {
+                            errors.push(format!("line {}: invalid count `{}`", i + 1, count));
+                            continue;
+                        }
+                    }
+                }
+                _ => (1, line),
+            };
+            match parse_card(card) {
+                Ok(card) => cards.extend(std::iter::repeat_n(card, count)),
+                Err(e) => errors.push(format!("line {}: {}", i + 1, e)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(Self { cards })
+        } else {
+            Err(errors.join("\n"))
+        }
+    }
+}
+
+fn card_line(card: &Card) -> String {
+    match card {
+        Card::Country(country) => {
+            let allowed_bonus = if country.allowed_bonus.is_empty() {
+                "-"
+            } else {
+                &country.allowed_bonus
+            };
+            format!(
+                "country {} {} {}",
+                country.name, country.score, allowed_bonus
+            )
+        }
+        Card::Bonus(bonus) => {
+            let name = match bonus {
+                Bonus::Beach => "beach",
+                Bonus::Culture => "culture",
+                Bonus::Trekking => "trekking",
+                Bonus::Wildlife => "wildlife",
+            };
+            format!("bonus {}", name)
+        }
+        Card::Advice(advice) => {
+            let good = if advice.good { "good" } else { "bad" };
+            let variant = match advice.variant {
+                AdviceType::Money => "money",
+                AdviceType::Bureaucracy => "bureaucracy",
+                AdviceType::Timing => "timing",
+                AdviceType::Transport => "transport",
+            };
+            format!("advice {} {}", good, variant)
+        }
+        Card::Grey(GreyType::MissedFlight) => "grey missed_flight".to_string(),
+        Card::Special(Special::CerditCard) => "special credit_card".to_string(),
+    }
+}
+
+/// Writes the deck in the file format read by `Deck::load`, one line per run of equal cards
+impl fmt::Display for Deck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut i = 0;
+        while i < self.cards.len() {
+            let line = card_line(&self.cards[i]);
+            let mut count = 1;
+            while self
+                .cards
+                .get(i + count)
+                .is_some_and(|card| card_line(card) == line)
+            {
+                count += 1;
+            }
+
+            if count == 1 {
+                writeln!(f, "{}", line)?;
+            } else {
+                writeln!(f, "{}x {}", count, line)?;
+            }
+            i += count;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Board, Rules};
+
+    #[test]
+    fn the_standard_deck_has_no_problems() {
+        assert!(Deck::standard().validate(4).is_ok());
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let deck: Deck = "country Atlantis 3 b\n\
+                          country France 4 bz\n\
+                          country France 4 bz\n\
+                          bonus wildlife"
+            .parse()
+            .unwrap();
+        let problems = deck.validate(2).unwrap_err().problems;
+        assert_eq!(
+            problems,
+            vec![
+                DeckProblem::UnknownContinent {
+                    country: "Atlantis".into()
+                },
+                DeckProblem::DuplicateCountry {
+                    country: "France".into(),
+                    copies: 2
+                },
+                DeckProblem::InvalidBonusLetter {
+                    country: "France".into(),
+                    letter: 'z'
+                },
+                DeckProblem::UnplayableBonus {
+                    bonus: "bonus wildlife".into()
+                },
+                DeckProblem::NotEnoughCards {
+                    players: 2,
+                    cards: 4
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn a_game_cannot_start_with_a_bad_bonus_letter() {
+        let mut deck = Deck::standard().to_string();
+        deck.push_str("country Atlantis 3 q\n");
+        let deck: Deck = deck.parse().unwrap();
+        assert!(Board::new_game_with_deck(2, Rules::default(), deck, 1).is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::BTreeMap;
+#[cfg(feature = "terminal")]
+use std::fmt;
+
+use serde::Serialize;
+#[cfg(feature = "terminal")]
+use tabular::{Row, Table};
+
+use crate::{Bonus, Card, Continent, Country, Deck, Special};
+
+/// The countries of one continent in a deck
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContinentReport {
+    pub continent: Continent,
+    pub countries: usize,
+    /// The scores of all its countries added up
+    pub score: u32,
+}
+
+/// How well one type of bonus is supported by a deck
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BonusReport {
+    pub bonus: Bonus,
+    /// Bonus cards of this type
+    pub cards: usize,
+    /// Countries it can be played on
+    pub countries: usize,
+}
+
+/// The shape of a deck, for checking the balance of a deck being edited
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename = "deck_report")]
+pub struct DeckReport {
+    pub cards: usize,
+    pub continents: Vec<ContinentReport>,
+    pub bonuses: Vec<BonusReport>,
+    /// The best pile that could be banked in one trip, with one country from each continent,
+    /// or two with a Credit Card in the deck, and every bonus card played
+    pub max_trip_score: u32,
+    /// The share of the deck that's grey cards, from 0 to 1
+    pub grey_ratio: f64,
+    pub advice_ratio: f64,
+    pub special_ratio: f64,
+}
+
+impl Deck {
+    /// Work out the deck's shape
+    pub fn report(&self) -> DeckReport {
+        let countries: Vec<&Country> = self.cards().iter().filter_map(Card::country).collect();
+
+        let mut by_continent: BTreeMap<Continent, Vec<&Country>> = BTreeMap::new();
+        for country in &countries {
+            by_continent
+                .entry(country.continent())
+                .or_default()
+                .push(country);
+        }
+        let continents = by_continent
+            .iter()
+            .map(|(continent, countries)| ContinentReport {
+                continent: *continent,
+                countries: countries.len(),
+                score: countries
+                    .iter()
+                    .map(|country| u32::from(country.score))
+                    .sum(),
+            })
+            .collect();
+
+        let bonus_cards = Bonus::ALL.map(|bonus| {
+            self.cards()
+                .iter()
+                .filter(|card| card.bonus() == Some(&bonus))
+                .count()
+        });
+        let bonuses = Bonus::ALL
+            .iter()
+            .zip(bonus_cards)
+            .map(|(bonus, cards)| BonusReport {
+                bonus: *bonus,
+                cards,
+                countries: countries
+                    .iter()
+                    .filter(|country| country.allowed_bonus.contains(bonus.unparse()))
+                    .count(),
+            })
+            .collect();
+
+        let has_credit_card = self
+            .cards()
+            .iter()
+            .any(|card| matches!(card, Card::Special(Special::CerditCard)));
+        let per_continent = if has_credit_card { 2 } else { 1 };
+
+        let ratio = |is_kind: fn(&Card) -> bool| {
+            let count = self.cards().iter().filter(|card| is_kind(card)).count();
+            count as f64 / self.len().max(1) as f64
+        };
+
+        DeckReport {
+            cards: self.len(),
+            continents,
+            bonuses,
+            max_trip_score: max_trip_score(&by_continent, bonus_cards, per_continent),
+            grey_ratio: ratio(|card| matches!(card, Card::Grey(_))),
+            advice_ratio: ratio(|card| matches!(card, Card::Advice(_))),
+            special_ratio: ratio(|card| matches!(card, Card::Special(_))),
+        }
+    }
+}
+
+/// The highest trip score from up to `per_continent` countries of each continent, with every
+/// bonus card played on the best scoring country in the pile that allows it.
+///
+/// A bonus scores its country again, so all that matters about the countries picked so far
+/// is their total and the best score each bonus could go on. Keeping the best total for each
+/// of those keeps the search small.
+fn max_trip_score(
+    by_continent: &BTreeMap<Continent, Vec<&Country>>,
+    bonus_cards: [usize; 4],
+    per_continent: usize,
+) -> u32 {
+    let mut best: BTreeMap<[u8; 4], u32> = BTreeMap::from([([0; 4], 0)]);
+
+    for countries in by_continent.values() {
+        // Every way of picking up to `per_continent` of this continent's countries
+        let mut picks: Vec<Vec<&Country>> = vec![Vec::new()];
+        for (i, country) in countries.iter().enumerate() {
+            picks.push(vec![country]);
+            if per_continent > 1 {
+                for other in &countries[i + 1..] {
+                    picks.push(vec![country, other]);
+                }
+            }
+        }
+
+        let mut next = BTreeMap::new();
+        for (bonus_scores, total) in &best {
+            for pick in &picks {
+                let mut bonus_scores = *bonus_scores;
+                let mut total = *total;
+                for country in pick {
+                    total += u32::from(country.score);
+                    for (bonus, best_score) in Bonus::ALL.iter().zip(&mut bonus_scores) {
+                        if country.allowed_bonus.contains(bonus.unparse()) {
+                            *best_score = (*best_score).max(country.score);
+                        }
+                    }
+                }
+                let entry = next.entry(bonus_scores).or_insert(0);
+                *entry = total.max(*entry);
+            }
+        }
+        best = next;
+    }
+
+    best.iter()
+        .map(|(bonus_scores, total)| {
+            let bonuses: u32 = bonus_scores
+                .iter()
+                .zip(bonus_cards)
+                .map(|(score, cards)| u32::from(*score) * cards as u32)
+                .sum();
+            total + bonuses
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(feature = "terminal")]
+impl fmt::Display for DeckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut table = Table::new("{:<}  {:>}  {:>}");
+        table.add_row(
+            Row::new()
+                .with_cell("Continent")
+                .with_cell("Countries")
+                .with_cell("Score"),
+        );
+        for continent in &self.continents {
+            table.add_row(
+                Row::new()
+                    .with_cell(format!("{:?}", continent.continent))
+                    .with_cell(continent.countries)
+                    .with_cell(continent.score),
+            );
+        }
+        writeln!(f, "{}", table)?;
+
+        let mut table = Table::new("{:<}  {:>}  {:>}");
+        table.add_row(
+            Row::new()
+                .with_cell("Bonus")
+                .with_cell("Cards")
+                .with_cell("Countries"),
+        );
+        for bonus in &self.bonuses {
+            table.add_row(
+                Row::new()
+                    .with_cell(format!("{:?}", bonus.bonus))
+                    .with_cell(bonus.cards)
+                    .with_cell(bonus.countries),
+            );
+        }
+        writeln!(f, "{}", table)?;
+
+        let percent = |ratio: f64| format!("{:.1}%", ratio * 100.0);
+        let mut table = Table::new("{:<}  {:>}");
+        table.add_row(Row::new().with_cell("Cards").with_cell(self.cards));
+        table.add_row(
+            Row::new()
+                .with_cell("Best trip score")
+                .with_cell(self.max_trip_score),
+        );
+        table.add_row(
+            Row::new()
+                .with_cell("Grey")
+                .with_cell(percent(self.grey_ratio)),
+        );
+        table.add_row(
+            Row::new()
+                .with_cell("Advice")
+                .with_cell(percent(self.advice_ratio)),
+        );
+        table.add_row(
+            Row::new()
+                .with_cell("Special")
+                .with_cell(percent(self.special_ratio)),
+        );
+        write!(f, "{}", table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL: &str = "country France 4 b\n\
+                         country Italy 5 c\n\
+                         country Kenya 3 bc\n\
+                         country Egypt 2 w\n\
+                         bonus beach\n\
+                         bonus culture\n\
+                         3x bonus wildlife\n";
+
+    #[test]
+    fn the_standard_deck_scores_170() {
+        assert_eq!(Deck::standard().report().max_trip_score, 170);
+    }
+
+    #[test]
+    fn the_best_pick_is_not_always_the_best_country() {
+        // Italy and Egypt, with the culture bonus on Italy and the wildlife bonuses on Egypt,
+        // beat Italy and Kenya, the highest scoring country of each continent
+        let deck: Deck = SMALL.parse().unwrap();
+        assert_eq!(deck.report().max_trip_score, 5 + 2 + 5 + 3 * 2);
+    }
+
+    #[test]
+    fn a_credit_card_allows_two_countries_a_continent() {
+        let deck: Deck = format!("{}special credit_card\n", SMALL).parse().unwrap();
+        assert_eq!(deck.report().max_trip_score, 4 + 5 + 3 + 2 + 4 + 5 + 3 * 2);
+    }
+}
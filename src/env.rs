@@ -0,0 +1,249 @@
+use crate::{
+    BError, Board, Bonus, Card, Deck, DrawCommand, DrawPile, HAND_SIZE, Move, Phase, Rules,
+    Session, SessionError, StatusType, TurnCommand,
+};
+
+const GO_HOME: usize = 2 * HAND_SIZE;
+const DRAW_DECK: usize = GO_HOME + 1;
+const DRAW_DISCARD: usize = GO_HOME + 2;
+
+/// What one step of the environment led to
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub observation: Vec<f32>,
+    /// Points the agent banked during the step
+    pub reward: f32,
+    pub done: bool,
+}
+
+/// A game for training an agent in seat 0 against bots in every other seat, with a fixed
+/// action space and a fixed-size observation of what the agent can see.
+///
+/// The observation is a list of numbers, made of, in order:
+///
+/// - the agent's hand, as a count of each kind of card
+/// - every seat's pile, as a count of each kind of card, starting with the agent
+/// - the bonuses on every seat's pile, as counts of beach, culture, trekking and wildlife
+/// - every seat's statuses: goes still to miss, and hazards in play
+/// - every seat's banked score and the score of their current pile
+/// - how many of each kind of card the agent hasn't seen: the deck and the other hands
+/// - the top discard, as a 1 for its kind of card
+/// - the size of the deck and of the discard pile
+/// - whether the agent is to move, and whether they are to draw
+///
+/// Kinds of card are the different cards in the standard deck, in their sorted order.
+pub struct Env {
+    num_players: usize,
+    rules: Rules,
+    opponent: String,
+    // One of each different card in the deck
+    kinds: Vec<Card>,
+    session: Option<Session>,
+}
+
+impl Env {
+    /// How many actions there are: play each hand slot, discard each hand slot, go home, draw
+    /// from the deck and take the top discard, in that order
+    pub const ACTIONS: usize = 2 * HAND_SIZE + 3;
+
+    /// An environment for games of `num_players`, with random bots as opponents. Call
+    /// `reset` to deal the first game.
+    pub fn new(num_players: usize, rules: Rules) -> Self {
+        let mut kinds = Deck::standard().cards().to_vec();
+        kinds.sort();
+        kinds.dedup();
+
+        Self {
+            num_players,
+            rules,
+            opponent: "random".to_string(),
+            kinds,
+            session: None,
+        }
+    }
+
+    /// Play every other seat with the strategy called `name`
+    pub fn with_opponent(mut self, name: &str) -> Self {
+        self.opponent = name.to_string();
+        self
+    }
+
+    /// How many numbers each observation holds
+    pub fn observation_size(&self) -> usize {
+        let (kinds, seats) = (self.kinds.len(), self.num_players);
+        kinds + seats * kinds + seats * Bonus::ALL.len() + seats * 2 + seats * 2 + 2 * kinds + 2 + 2
+    }
+
+    /// Deal a new game with `seed` deciding the deal and the bots' choices, and play the bots'
+    /// turns up to the agent's first
+    pub fn reset(&mut self, seed: u64) -> Result<Vec<f32>, BError> {
+        let mut bots = vec![Some(self.opponent.clone()); self.num_players];
+        if let Some(agent) = bots.first_mut() {
+            *agent = None;
+        }
+        self.session = Some(Session::new(
+            self.num_players,
+            self.rules.clone(),
+            seed,
+            &bots,
+        )?);
+        Ok(self.observation())
+    }
+
+    /// Take `action` for the agent and play the bots' turns up to the agent's next decision.
+    /// Rejects actions the mask rules out, leaving the game as it was. Before the first
+    /// `reset` there's no game, as if one had ended.
+    pub fn step(&mut self, action: usize) -> Result<Step, SessionError> {
+        let allowed = self.action_mask();
+        let session = self.session.as_mut().ok_or(SessionError::GameOver)?;
+        if session.board().is_over() {
+            return Err(SessionError::GameOver);
+        }
+        if !allowed.get(action).copied().unwrap_or(false) {
+            let message = format!("action {} isn't allowed now", action);
+            return Err(SessionError::Rejected(BError::Custom(message)));
+        }
+
+        let command = match action {
+            GO_HOME => TurnCommand::Move(Move::GoHome),
+            DRAW_DECK => draw(DrawPile::Deck),
+            DRAW_DISCARD => draw(DrawPile::Discard),
+            i if i < HAND_SIZE => TurnCommand::Move(Move::Play(i)),
+            i => TurnCommand::Move(Move::Discard(i - HAND_SIZE)),
+        };
+        let before = session.board().scores()[0];
+        session.play(0, command)?;
+
+        let board = session.board();
+        Ok(Step {
+            reward: (board.scores()[0] - before) as f32,
+            done: board.is_over(),
+            observation: self.observation(),
+        })
+    }
+
+    /// Which actions the agent can take now, in action order
+    pub fn action_mask(&self) -> [bool; Self::ACTIONS] {
+        let mut mask = [false; Self::ACTIONS];
+        let Some(session) = &self.session else {
+            return mask;
+        };
+        let board = session.board();
+        if board.is_over() || board.turn != 0 {
+            return mask;
+        }
+
+        match session.phase() {
+            Phase::Move => {
+                for mv in board.legal_moves() {
+                    let action = match mv {
+                        Move::Play(i) => i,
+                        Move::Discard(i) => HAND_SIZE + i,
+                        Move::GoHome => GO_HOME,
+                    };
+                    mask[action] = true;
+                }
+            }
+            Phase::Draw => {
+                mask[DRAW_DECK] = true;
+                mask[DRAW_DISCARD] = board.can_take_discard();
+            }
+            Phase::GameOver => {}
+        }
+        mask
+    }
+
+    /// What the agent can see, encoded as described on `Env`. All zeros before the first
+    /// `reset`.
+    pub fn observation(&self) -> Vec<f32> {
+        let Some(session) = &self.session else {
+            return vec![0.0; self.observation_size()];
+        };
+        let board = session.board();
+        let agent = &board.players[0];
+        let mut observation = Vec::with_capacity(self.observation_size());
+
+        observation.extend(self.counts(&agent.hand));
+        for player in &board.players {
+            let pile: Vec<Card> = player.pile.iter().cloned().map(Card::Country).collect();
+            observation.extend(self.counts(&pile));
+        }
+        for player in &board.players {
+            observation.extend(Bonus::ALL.iter().map(|bonus| {
+                let attached = player.pile.iter().flat_map(|country| &country.bonus);
+                attached.filter(|attached| *attached == bonus).count() as f32
+            }));
+        }
+        for player in &board.players {
+            let missed_goes: u32 = player
+                .status
+                .hazards
+                .iter()
+                .map(|hazard| &hazard.status)
+                .chain(&player.status.types)
+                .map(|status| match status {
+                    StatusType::MissGo(gos) => u32::from(*gos),
+                    _ => 0,
+                })
+                .sum();
+            observation.push(missed_goes as f32);
+            observation.push(player.status.hazards.len() as f32);
+        }
+        for player in &board.players {
+            observation.push(player.score as f32);
+            observation.push(player.trip_score() as f32);
+        }
+
+        observation.extend(self.unseen(board));
+        let top = board.top_discard().map(std::slice::from_ref);
+        observation.extend(self.counts(top.unwrap_or_default()));
+        observation.push(board.future.len() as f32);
+        observation.push(board.past.len() as f32);
+
+        let to_act = board.turn == 0 && !board.is_over();
+        observation.push(f32::from(u8::from(
+            to_act && session.phase() == Phase::Move,
+        )));
+        observation.push(f32::from(u8::from(
+            to_act && session.phase() == Phase::Draw,
+        )));
+
+        debug_assert_eq!(observation.len(), self.observation_size());
+        observation
+    }
+
+    pub fn session(&self) -> Option<&Session> {
+        self.session.as_ref()
+    }
+
+    fn kind(&self, card: &Card) -> usize {
+        // Countries are equal by name, so one with bonuses attached is still found
+        self.kinds
+            .iter()
+            .position(|kind| kind == card)
+            .expect("Every card comes from the standard deck")
+    }
+
+    fn counts(&self, cards: &[Card]) -> Vec<f32> {
+        let mut counts = vec![0.0; self.kinds.len()];
+        for card in cards {
+            counts[self.kind(card)] += 1.0;
+        }
+        counts
+    }
+
+    // Cards in the deck or in another player's hand: everything the agent hasn't seen
+    fn unseen(&self, board: &Board) -> Vec<f32> {
+        let mut unseen = self.counts(&board.future);
+        for player in &board.players[1..] {
+            for (count, held) in unseen.iter_mut().zip(self.counts(&player.hand)) {
+                *count += held;
+            }
+        }
+        unseen
+    }
+}
+
+fn draw(from: DrawPile) -> TurnCommand {
+    TurnCommand::Draw(DrawCommand::Draw { from })
+}
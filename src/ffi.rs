@@ -0,0 +1,289 @@
+//! A C API for driving games from other languages. `backpacker.h` is generated from this
+//! module into the build's `OUT_DIR` when building with the `ffi` feature, and copied to
+//! `include/` when `BACKPACKER_WRITE_HEADER` is set.
+//!
+//! Fallible functions return a `BpStatus`, with `BP_STATUS_OK` for success, and
+//! `bp_last_error` describes the latest failure on the calling thread. States, moves and
+//! events are JSON strings in the same shapes the HTTP server uses, to be freed with
+//! `bp_string_free`.
+
+use std::{
+    cell::RefCell,
+    ffi::{CStr, CString, c_char},
+};
+
+use serde::Serialize;
+
+use crate::{BError, Rules, Session, SessionError, TurnCommand};
+
+/// A game in progress, created with `bp_game_new` and freed with `bp_game_free`
+pub struct Game {
+    session: Session,
+}
+
+/// What went wrong in a call, or `Ok`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok = 0,
+    /// A pointer was null, or a string wasn't UTF-8 or the JSON expected
+    InvalidArgument,
+    /// The seat number is past the last seat
+    NoSeat,
+    GameOver,
+    /// Only the player whose turn it is can move
+    NotYourTurn,
+    /// A move has to be made before drawing
+    MoveFirst,
+    /// The player has moved and has to draw next
+    DrawNext,
+    // The rest match `BError`
+    Custom,
+    SameContinent,
+    GreyHeld,
+    InvalidBonus,
+    NoTopCountry,
+    Unplayable,
+    InvalidIndex,
+    NotGrey,
+    NoPlayers,
+    NotEnoughCards,
+}
+
+impl From<&BError> for Status {
+    fn from(e: &BError) -> Self {
+        match e {
+            BError::Custom(_) => Self::Custom,
+            BError::SameContinent => Self::SameContinent,
+            BError::GreyHeld => Self::GreyHeld,
+            BError::InvalidBonus => Self::InvalidBonus,
+            BError::NoTopCountry => Self::NoTopCountry,
+            BError::Unplayable => Self::Unplayable,
+            BError::InvalidIndex => Self::InvalidIndex,
+            BError::NotGrey => Self::NotGrey,
+            BError::NoPlayers => Self::NoPlayers,
+            BError::NotEnoughCards { .. } => Self::NotEnoughCards,
+        }
+    }
+}
+
+impl From<&SessionError> for Status {
+    fn from(e: &SessionError) -> Self {
+        match e {
+            SessionError::GameOver => Self::GameOver,
+            SessionError::NotYourTurn { .. } => Self::NotYourTurn,
+            SessionError::MoveFirst => Self::MoveFirst,
+            SessionError::DrawNext => Self::DrawNext,
+            SessionError::Rejected(e) => e.into(),
+        }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::default();
+}
+
+// Remember `message` for `bp_last_error` and hand back `status`
+fn fail(status: Status, message: impl ToString) -> Status {
+    // Messages never hold a nul, but one would only cut the message short
+    let message = message.to_string().replace('\0', " ");
+    LAST_ERROR.with(|last| *last.borrow_mut() = CString::new(message).unwrap_or_default());
+    status
+}
+
+// The game behind `game`, with the seat checked
+unsafe fn session<'a>(game: *const Game, seat: usize) -> Result<&'a Session, Status> {
+    // SAFETY: the caller passes null or a pointer from `bp_game_new`
+    let game = unsafe { game.as_ref() }.ok_or_else(|| fail(Status::InvalidArgument, "no game"))?;
+    if seat >= game.session.players() {
+        return Err(fail(Status::NoSeat, format!("no seat {}", seat)));
+    }
+    Ok(&game.session)
+}
+
+// Hand `view` to the caller as a JSON string in `json`
+unsafe fn write_json(view: impl Serialize, json: *mut *mut c_char) -> Status {
+    if json.is_null() {
+        return fail(Status::InvalidArgument, "nowhere to put the JSON");
+    }
+    let string = serde_json::to_string(&view).expect("Views always serialize");
+    let string = CString::new(string).expect("JSON never holds a nul");
+    // SAFETY: checked for null above, and the caller passes somewhere to write a pointer
+    unsafe { *json = string.into_raw() };
+    Status::Ok
+}
+
+/// Deal a game for `players` seats, with `seed` deciding the deal, and put it in `game`
+///
+/// # Safety
+///
+/// `game` must be null or point somewhere a pointer can be written.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bp_game_new(players: usize, seed: u64, game: *mut *mut Game) -> Status {
+    if game.is_null() {
+        return fail(Status::InvalidArgument, "nowhere to put the game");
+    }
+    match Session::new(players, Rules::default(), seed, &[]) {
+        Ok(session) => {
+            // SAFETY: checked for null above
+            unsafe { *game = Box::into_raw(Box::new(Game { session })) };
+            Status::Ok
+        }
+        Err(e) => fail((&e).into(), e),
+    }
+}
+
+/// Free a game made by `bp_game_new`. Does nothing with null.
+///
+/// # Safety
+///
+/// `game` must be null or a pointer from `bp_game_new` that hasn't been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bp_game_free(game: *mut Game) {
+    if !game.is_null() {
+        // SAFETY: the pointer came from `Box::into_raw` in `bp_game_new`
+        drop(unsafe { Box::from_raw(game) });
+    }
+}
+
+/// Whose turn it is, counting seats from 0, or `SIZE_MAX` with no game
+///
+/// # Safety
+///
+/// `game` must be null or a live pointer from `bp_game_new`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bp_game_turn(game: *const Game) -> usize {
+    // SAFETY: the caller passes null or a live game
+    match unsafe { game.as_ref() } {
+        Some(game) => game.session.board().turn,
+        None => {
+            fail(Status::InvalidArgument, "no game");
+            usize::MAX
+        }
+    }
+}
+
+/// Whether the game has finished, which a missing game counts as so loops over it stop
+///
+/// # Safety
+///
+/// `game` must be null or a live pointer from `bp_game_new`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bp_game_is_over(game: *const Game) -> bool {
+    // SAFETY: the caller passes null or a live game
+    match unsafe { game.as_ref() } {
+        Some(game) => game.session.board().is_over(),
+        None => {
+            fail(Status::InvalidArgument, "no game");
+            true
+        }
+    }
+}
+
+/// Put everything `seat` can see in `json`, including its own hand but nobody else's
+///
+/// # Safety
+///
+/// `game` must be null or a live pointer from `bp_game_new`, and `json` null or somewhere
+/// a pointer can be written.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bp_game_view(
+    game: *const Game,
+    seat: usize,
+    json: *mut *mut c_char,
+) -> Status {
+    match unsafe { session(game, seat) } {
+        Ok(session) => unsafe { write_json(session.view(seat), json) },
+        Err(status) => status,
+    }
+}
+
+/// Put what `seat` can do now in `json`, as `{"phase": "move", "legal_moves": [..]}`
+///
+/// # Safety
+///
+/// As for `bp_game_view`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bp_game_legal_moves(
+    game: *const Game,
+    seat: usize,
+    json: *mut *mut c_char,
+) -> Status {
+    match unsafe { session(game, seat) } {
+        Ok(session) => unsafe { write_json(session.moves(seat), json) },
+        Err(status) => status,
+    }
+}
+
+/// Put the events after the first `since` in `json`, as
+/// `{"events": [..], "next": 3, "over": false}`
+///
+/// # Safety
+///
+/// As for `bp_game_view`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bp_game_events(
+    game: *const Game,
+    since: usize,
+    json: *mut *mut c_char,
+) -> Status {
+    match unsafe { session(game, 0) } {
+        Ok(session) => unsafe { write_json(session.events(since), json) },
+        Err(status) => status,
+    }
+}
+
+/// Play a move like `{"command": "play", "index": 0}` for `seat`, or
+/// `{"command": "draw", "from": "deck"}` once it has moved
+///
+/// # Safety
+///
+/// `game` must be null or a live pointer from `bp_game_new`, and `command` null or a
+/// nul-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bp_game_apply_move(
+    game: *mut Game,
+    seat: usize,
+    command: *const c_char,
+) -> Status {
+    if let Err(status) = unsafe { session(game, seat) } {
+        return status;
+    }
+    if command.is_null() {
+        return fail(Status::InvalidArgument, "no move");
+    }
+    // SAFETY: the caller passes a nul-terminated string
+    let command = unsafe { CStr::from_ptr(command) };
+    let command: TurnCommand = match command.to_str().map(serde_json::from_str) {
+        Ok(Ok(command)) => command,
+        Ok(Err(e)) => return fail(Status::InvalidArgument, e),
+        Err(e) => return fail(Status::InvalidArgument, e),
+    };
+
+    // SAFETY: checked by `session` above
+    let game = unsafe { &mut *game };
+    match game.session.play(seat, command) {
+        Ok(()) => Status::Ok,
+        Err(e) => fail((&e).into(), e),
+    }
+}
+
+/// Free a string returned by this library. Does nothing with null.
+///
+/// # Safety
+///
+/// `string` must be null or a string from this library that hasn't been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bp_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        // SAFETY: the string came from `CString::into_raw` in `write_json`
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// A description of the latest failure on this thread, or an empty string. Stays valid
+/// until the next failing call on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn bp_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ptr())
+}
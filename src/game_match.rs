@@ -0,0 +1,216 @@
+use std::{fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+use tabular::{Row, Table};
+
+use crate::{
+    Board, OutputFormat, Profiles, Replay, Rules, Spectators, TurnTimer,
+    jsonl::{Message, emit},
+    locale::{tr, trf},
+};
+
+/// A series of games between the same players, with scores carried across games
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Match {
+    num_players: usize,
+    num_games: usize,
+    rules: Rules,
+    // Player names, indexed by seat. `None`, or missing, for the default "Player N" names,
+    // which aren't added to profiles.
+    #[serde(default)]
+    names: Vec<Option<String>>,
+    // Each finished game's scores, indexed by seat
+    results: Vec<Vec<u32>>,
+    // The game that was being played when the match was last left, to carry on from
+    #[serde(default)]
+    current: Option<Replay>,
+    // Carried from game to game, and chosen again on resume
+    #[serde(skip)]
+    timer: Option<TurnTimer>,
+    #[serde(skip)]
+    spectators: Option<Spectators>,
+}
+
+impl Match {
+    pub fn new(num_players: usize, num_games: usize, rules: Rules) -> Self {
+        Self {
+            num_players,
+            num_games,
+            rules,
+            names: Vec::new(),
+            results: Vec::new(),
+            current: None,
+            timer: None,
+            spectators: None,
+        }
+    }
+
+    pub fn with_names(mut self, names: Vec<Option<String>>) -> Self {
+        self.names = names;
+        self
+    }
+
+    /// Limit how long each turn can take
+    pub fn with_turn_timer(mut self, timer: Option<TurnTimer>) -> Self {
+        self.timer = timer;
+        self
+    }
+
+    /// Let people watch every game of the match
+    pub fn with_spectators(mut self, spectators: Option<Spectators>) -> Self {
+        self.spectators = spectators;
+        self
+    }
+
+    fn name(&self, seat: usize) -> String {
+        match self.names.get(seat) {
+            Some(Some(name)) => name.clone(),
+            _ => trf("player.default_name", &[&(seat + 1)]),
+        }
+    }
+
+    /// Seats given a name of their own
+    fn named_seats(&self) -> Vec<usize> {
+        (0..self.num_players)
+            .filter(|&seat| matches!(self.names.get(seat), Some(Some(_))))
+            .collect()
+    }
+
+    /// Load a match saved by `Match::save`
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        serde_json::from_str(&contents).map_err(io::Error::other)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let contents = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, contents)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.results.len() >= self.num_games
+    }
+
+    /// The seat that starts the next game, moving one seat along each game
+    fn first_player(&self) -> usize {
+        self.results.len() % self.num_players
+    }
+
+    /// Total score of each seat across all finished games
+    pub fn standings(&self) -> Vec<u32> {
+        let mut totals = vec![0; self.num_players];
+        for game in &self.results {
+            for (total, score) in totals.iter_mut().zip(game) {
+                *total += score;
+            }
+        }
+        totals
+    }
+
+    /// Play the remaining games, carrying on with any game left unfinished, and add each one
+    /// to the players' profiles. Progress is saved to `save_path` if given after each game,
+    /// and when a game is left unfinished.
+    pub fn play(
+        &mut self,
+        save_path: Option<&Path>,
+        mut profiles: Option<&mut Profiles>,
+    ) -> io::Result<()> {
+        while !self.is_finished() {
+            if OutputFormat::current() == OutputFormat::Jsonl {
+                emit(&Message::MatchGame {
+                    game: self.results.len() + 1,
+                    games: self.num_games,
+                });
+            } else {
+                println!(
+                    "{}",
+                    trf("match.game", &[&(self.results.len() + 1), &self.num_games])
+                );
+            }
+
+            let mut board = match self.current.take() {
+                Some(replay) => replay.resume().map_err(io::Error::other)?,
+                None => self.new_board()?,
+            };
+            board.set_turn_timer(self.timer.take());
+            board.set_spectators(self.spectators.take());
+            board.manual_game();
+            self.timer = board.take_turn_timer();
+            self.spectators = board.take_spectators();
+            if board.is_abandoned() {
+                self.current = Some(board.replay());
+                if let Some(path) = save_path {
+                    self.save(path)?;
+                }
+                return Ok(());
+            }
+            self.results.push(board.scores());
+
+            if let Some(profiles) = profiles.as_deref_mut() {
+                profiles.record_game(&board, &self.named_seats());
+                profiles.save()?;
+            }
+
+            if let Some(path) = save_path {
+                self.save(path)?;
+            }
+
+            self.show_standings();
+        }
+
+        Ok(())
+    }
+
+    /// Deal the next game of the match
+    fn new_board(&self) -> io::Result<Board> {
+        let mut board = Board::new_game_with_rules(self.num_players, self.rules.clone())
+            .map_err(io::Error::other)?;
+        let names: Vec<String> = (0..self.num_players).map(|seat| self.name(seat)).collect();
+        board.set_names(&names);
+        board.set_first_player(self.first_player());
+        Ok(board)
+    }
+
+    pub fn show_standings(&self) {
+        if OutputFormat::current() == OutputFormat::Jsonl {
+            emit(&Message::Standings {
+                names: (0..self.num_players).map(|seat| self.name(seat)).collect(),
+                results: &self.results,
+                totals: self.standings(),
+            });
+            return;
+        }
+
+        let mut spec = String::from("{:<}");
+        for _ in 0..self.results.len() {
+            spec.push_str("  {:>}");
+        }
+        spec.push_str("  {:>}");
+
+        let mut table = Table::new(&spec);
+
+        let mut heading = Row::new().with_cell("");
+        for game in 1..=self.results.len() {
+            heading.add_cell(trf("match.game_column", &[&game]));
+        }
+        heading.add_cell(tr("match.total"));
+        table.add_row(heading);
+
+        for (seat, total) in self.standings().iter().enumerate() {
+            let mut row = Row::new().with_cell(self.name(seat));
+            for game in &self.results {
+                row.add_cell(game[seat]);
+            }
+            row.add_cell(total);
+            table.add_row(row);
+        }
+
+        println!();
+        println!(
+            "{}",
+            trf("match.standings", &[&self.results.len(), &self.num_games])
+        );
+        print!("{}", table);
+        println!();
+    }
+}
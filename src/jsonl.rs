@@ -0,0 +1,213 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    Board, Card, Event, HAND_SIZE, Move, Strategy, read_line,
+    session::{DrawCommand, DrawPile, PlayerState},
+};
+
+/// How the game talks to whoever is playing it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Text for people, read from and written to a terminal
+    Human,
+    /// One JSON object per line on stdout, with JSON commands read from stdin
+    Jsonl,
+}
+
+static OUTPUT_FORMAT: AtomicU8 = AtomicU8::new(OutputFormat::Human as u8);
+
+impl OutputFormat {
+    pub fn current() -> Self {
+        match OUTPUT_FORMAT.load(Ordering::Relaxed) {
+            1 => Self::Jsonl,
+            _ => Self::Human,
+        }
+    }
+
+    /// Play games in this format from now on
+    pub fn set(self) {
+        OUTPUT_FORMAT.store(self as u8, Ordering::Relaxed);
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "human" => Some(Self::Human),
+            "jsonl" => Some(Self::Jsonl),
+            _ => None,
+        }
+    }
+}
+
+/// A line written to stdout in JSON-lines mode
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum Message<'a> {
+    GameStarted {
+        names: Vec<String>,
+        hand_size: usize,
+        deck: usize,
+    },
+    State {
+        seat: usize,
+        hand: &'a [Card],
+        discard: Option<&'a Card>,
+        deck: usize,
+        players: Vec<PlayerState<'a>>,
+    },
+    Event(&'a Event),
+    Prompt(Prompt<'a>),
+    Error {
+        message: String,
+    },
+    GameOver {
+        scores: Vec<u32>,
+        winners: Vec<usize>,
+    },
+    MatchGame {
+        game: usize,
+        games: usize,
+    },
+    Standings {
+        names: Vec<String>,
+        results: &'a [Vec<u32>],
+        totals: Vec<u32>,
+    },
+    /// What spectators see after each turn: everything but the cards in players' hands
+    PublicState {
+        seat: usize,
+        discard: Option<&'a Card>,
+        discard_size: usize,
+        deck: usize,
+        players: Vec<PlayerState<'a>>,
+    },
+    /// Every player's hand at the end of an earlier turn, counting from 1
+    Reveal {
+        turn: usize,
+        hands: &'a [Vec<Card>],
+    },
+}
+
+/// What the current player is being asked for, and the command that answers it
+#[derive(Serialize)]
+#[serde(tag = "prompt", rename_all = "snake_case")]
+pub(crate) enum Prompt<'a> {
+    /// Answered with a move, e.g. `{"command": "play", "index": 0}` or `{"command": "go_home"}`
+    Move { seat: usize, legal_moves: Vec<Move> },
+    /// Answered with `{"command": "draw", "from": "deck"}` or `"from": "discard"`
+    Draw { seat: usize, discard: &'a Card },
+}
+
+/// Write one message as a line of JSON
+pub(crate) fn emit(message: &Message) {
+    println!(
+        "{}",
+        serde_json::to_string(message).expect("Messages always serialize")
+    );
+}
+
+/// Read the next command from stdin, reporting any line that isn't one.
+/// Returns `None` once stdin is closed.
+fn read_command<T: DeserializeOwned>() -> Option<T> {
+    loop {
+        let line = read_line().ok()?;
+        if line.is_empty() {
+            return None;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str(&line) {
+            Ok(command) => return Some(command),
+            Err(e) => emit(&Message::Error {
+                message: e.to_string(),
+            }),
+        }
+    }
+}
+
+impl Board {
+    fn state(&self) -> Message<'_> {
+        Message::State {
+            seat: self.turn,
+            hand: &self.players[self.turn].hand,
+            discard: self.top_discard(),
+            deck: self.future.len(),
+            players: self.player_states(),
+        }
+    }
+
+    /// Write any events that haven't been written yet
+    fn emit_events(&mut self) {
+        for event in &self.events[self.shown_events..] {
+            emit(&Message::Event(event));
+        }
+        self.shown_events = self.events.len();
+    }
+
+    /// Play a game over stdin and stdout in JSON-lines mode, stopping early if stdin closes.
+    /// Seats with a bot are played without any prompts.
+    pub(crate) fn jsonl_game(&mut self, bots: &mut [Option<Box<dyn Strategy>>]) {
+        emit(&Message::GameStarted {
+            names: self.names(),
+            hand_size: HAND_SIZE,
+            deck: self.future.len(),
+        });
+
+        while !self.is_over() {
+            if let Some(bot) = bots[self.turn].as_deref_mut() {
+                self.bot_turn(bot);
+                self.emit_events();
+                continue;
+            }
+
+            emit(&self.state());
+            if !self.begin_turn() {
+                self.emit_events();
+                continue;
+            }
+            self.emit_events();
+
+            loop {
+                emit(&Message::Prompt(Prompt::Move {
+                    seat: self.turn,
+                    legal_moves: self.legal_moves(),
+                }));
+                let Some(mv) = read_command::<Move>() else {
+                    self.abandon();
+                    return;
+                };
+                match self.apply_move(mv) {
+                    Ok(()) => break,
+                    Err(e) => emit(&Message::Error {
+                        message: e.to_string(),
+                    }),
+                }
+            }
+            self.emit_events();
+
+            let mut from_discard = false;
+            if self.can_take_discard() {
+                emit(&Message::Prompt(Prompt::Draw {
+                    seat: self.turn,
+                    discard: self.top_discard().unwrap(),
+                }));
+                let Some(DrawCommand::Draw { from }) = read_command() else {
+                    self.abandon();
+                    return;
+                };
+                from_discard = matches!(from, DrawPile::Discard);
+            }
+
+            self.end_turn(from_discard);
+            self.emit_events();
+        }
+
+        emit(&Message::GameOver {
+            scores: self.scores(),
+            winners: self.winners(),
+        });
+    }
+}
@@ -1,16 +1,117 @@
 #![allow(private_interfaces)]
 
-use std::{error::Error, fmt, io, thread::sleep, time::Duration};
-
-use rand::prelude::*;
-use text_io::try_read;
-
+use std::{error::Error, fmt};
+#[cfg(feature = "terminal")]
+use std::{
+    io,
+    sync::atomic::{AtomicU64, Ordering},
+    thread::sleep,
+    time::Duration,
+};
+
+use rand::{prelude::*, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "terminal")]
+use crate::command::{Command, Kind, find_card, parse_command};
+use crate::locale::{tr, trf};
+
+mod audit;
+mod bot;
+mod boxes;
+#[cfg(feature = "terminal")]
+mod command;
+mod deck;
+mod deck_report;
+mod env;
+#[cfg(feature = "ffi")]
+mod ffi;
+#[cfg(feature = "terminal")]
+mod game_match;
+#[cfg(feature = "terminal")]
+mod jsonl;
+mod locale;
 mod looks;
+#[cfg(feature = "terminal")]
+mod meta;
+#[cfg(feature = "terminal")]
+mod profile;
+#[cfg(feature = "python")]
+mod python;
+mod replay;
+mod risk;
+#[cfg(feature = "terminal")]
+mod server;
+mod session;
+#[cfg(feature = "terminal")]
+mod spectate;
+#[cfg(feature = "terminal")]
+mod style;
+// Cards are drawn as plain text without a terminal to colour them
+#[cfg(not(feature = "terminal"))]
+#[path = "plain.rs"]
+mod style;
+#[cfg(feature = "terminal")]
+mod timer;
+#[cfg(feature = "terminal")]
+mod tournament;
+mod tracker;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+pub use audit::CardLeak;
+pub use boxes::CardStyle;
+pub use bot::{Strategy, StrategyClone, strategy, strategy_names};
+pub use deck::{Deck, DeckProblem, DeckProblems};
+pub use deck_report::{BonusReport, ContinentReport, DeckReport};
+pub use env::{Env, Step};
+#[cfg(feature = "terminal")]
+pub use game_match::Match;
+#[cfg(feature = "terminal")]
+pub use jsonl::OutputFormat;
+pub use locale::Locale;
+#[cfg(feature = "terminal")]
+pub use profile::{Profile, Profiles};
+pub use replay::{Action, Replay};
+pub use risk::GoHomeRisk;
+#[cfg(feature = "terminal")]
+pub use server::GameServer;
+pub use session::{
+    DrawCommand, DrawPile, EventsView, MovesView, Phase, SeatView, Session, SessionError,
+    TurnCommand,
+};
+#[cfg(feature = "terminal")]
+pub use spectate::Spectators;
+#[cfg(feature = "terminal")]
+pub use style::{Theme, detect_colour, set_colour, set_tags};
+#[cfg(feature = "terminal")]
+pub use timer::{Clock, DefaultAction, FakeClock, SystemClock, TurnTimer};
+#[cfg(feature = "terminal")]
+pub use tournament::{Results, Standing, Tournament};
+pub use tracker::set_card_tracker;
+#[cfg(feature = "wasm")]
+pub use wasm::Game;
 
 const HAND_SIZE: usize = 5;
+#[cfg(feature = "terminal")]
 const PAUSE_TIME: u64 = 700;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+#[cfg(feature = "terminal")]
+static PACE: AtomicU64 = AtomicU64::new(PAUSE_TIME);
+
+/// How long to wait, in milliseconds, after each step of a game shown at the terminal
+#[cfg(feature = "terminal")]
+pub fn set_pace(millis: u64) {
+    PACE.store(millis, Ordering::Relaxed);
+}
+
+#[cfg(feature = "terminal")]
+fn pause() {
+    sleep(Duration::from_millis(PACE.load(Ordering::Relaxed)));
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
 enum Bonus {
     Beach,
     Culture,
@@ -19,6 +120,8 @@ enum Bonus {
 }
 
 impl Bonus {
+    const ALL: [Self; 4] = [Self::Beach, Self::Culture, Self::Trekking, Self::Wildlife];
+
     fn parse(input: &char) -> Self {
         match input {
             'b' => Self::Beach,
@@ -39,7 +142,7 @@ impl Bonus {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 enum Continent {
     Africa,
     America,
@@ -67,8 +170,9 @@ impl Country {
         }
     }
 
-    fn continent(&self) -> Continent {
-        match self.name.as_str() {
+    /// The continent the country is on, or `None` for a name that isn't known
+    fn known_continent(&self) -> Option<Continent> {
+        Some(match self.name.as_str() {
             "Mali" | "Egypt" | "Kenya" | "Morocco" | "Uganda" | "South Africa" | "Zimbabwe" => {
                 Continent::Africa
             }
@@ -84,8 +188,12 @@ impl Country {
             "Easter Island" | "Tahiti" | "New Zealand" | "Australia" | "Cook Islands" | "Fiji" => {
                 Continent::Oceania
             }
-            _ => Continent::Antarctica,
-        }
+            _ => return None,
+        })
+    }
+
+    fn continent(&self) -> Continent {
+        self.known_continent().unwrap_or(Continent::Antarctica)
     }
 }
 
@@ -95,7 +203,8 @@ impl PartialEq for Country {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
 enum AdviceType {
     Money,
     Bureaucracy,
@@ -103,7 +212,7 @@ enum AdviceType {
     Transport,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 struct Advice {
     good: bool,
     variant: AdviceType,
@@ -115,17 +224,20 @@ impl Advice {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
 enum GreyType {
     MissedFlight,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 enum Special {
+    #[serde(rename = "credit_card")]
     CerditCard,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
 enum Card {
     Country(Country),
     Bonus(Bonus),
@@ -135,6 +247,7 @@ enum Card {
 }
 
 impl Card {
+    #[allow(dead_code)]
     fn is_country(&self) -> bool {
         matches!(self, Card::Country(..))
     }
@@ -147,6 +260,7 @@ impl Card {
         }
     }
 
+    #[allow(dead_code)]
     fn is_bonus(&self) -> bool {
         matches!(self, Card::Bonus(..))
     }
@@ -160,67 +274,102 @@ impl Card {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 enum StatusType {
     // Player will miss their next go(s)
     MissGo(u8),
     // ??
+    #[allow(dead_code)]
     NoCountries(u8),
     // Affected by Bad Advice
+    #[allow(dead_code)]
     BadAdvice(AdviceType),
     // Affected by Visa Problem
+    #[allow(dead_code)]
     VisaProblem,
 }
 
-#[derive(Debug)]
+/// A played grey card, kept in play for as long as the status it caused lasts
+#[derive(Debug, Clone)]
+struct Hazard {
+    card: GreyType,
+    status: StatusType,
+}
+
+impl Hazard {
+    fn is_active(&self) -> bool {
+        match self.status {
+            StatusType::MissGo(gos) => gos != 0,
+            _ => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 struct StatusHandler {
     types: Vec<StatusType>,
+    // Grey cards in play, each with the status it caused
+    hazards: Vec<Hazard>,
 }
 
 impl StatusHandler {
     fn empty() -> Self {
-        Self { types: Vec::new() }
+        Self {
+            types: Vec::new(),
+            hazards: Vec::new(),
+        }
     }
 
+    #[allow(dead_code)]
     fn add_status(&mut self, status: StatusType) {
         self.types.push(status);
     }
 
+    fn add_hazard(&mut self, card: GreyType, status: StatusType) {
+        self.hazards.push(Hazard { card, status });
+    }
+
+    #[allow(dead_code)]
     fn remove_status(&mut self, status: StatusType) {
         self.types.retain(|t| *t != status);
     }
 
-    fn no_turn(&mut self) -> bool {
-        for ty in self.types.iter_mut() {
+    fn statuses_mut(&mut self) -> impl Iterator<Item = &mut StatusType> {
+        self.types
+            .iter_mut()
+            .chain(self.hazards.iter_mut().map(|hazard| &mut hazard.status))
+    }
+
+    /// Use up one missed go if the player has any, returning how many more they still have to miss
+    fn no_turn(&mut self) -> Option<u8> {
+        for ty in self.statuses_mut() {
             if let StatusType::MissGo(gos) = ty {
-                if gos > &mut 1 {
-                    println!("Missing this go. {} more to go", gos);
-                } else {
-                    println!("Missing this go.");
-                }
                 *gos -= 1;
-
-                self.cleanup();
-
-                sleep(Duration::from_millis(PAUSE_TIME));
-                return true;
+                return Some(*gos);
             }
         }
 
-        // sleep(Duration::from_millis(PAUSE_TIME));
-        false
+        None
     }
 
-    fn cleanup(&mut self) {
+    /// Drop any statuses that have run out, returning the hazard cards that caused them
+    fn cleanup(&mut self) -> Vec<Card> {
         self.types.retain(|ty| match ty {
             StatusType::MissGo(gos) => *gos != 0,
             _ => true,
         });
+
+        let (active, expired) = self.hazards.drain(..).partition(Hazard::is_active);
+        self.hazards = active;
+        expired
+            .into_iter()
+            .map(|hazard: Hazard| Card::Grey(hazard.card))
+            .collect()
     }
 }
 
 #[derive(Debug)]
-enum BError {
+pub enum BError {
     Custom(String),
     // Attempted to play countries of the same continent, (2 without credit card or 3 with credit card)
     SameContinent,
@@ -230,6 +379,16 @@ enum BError {
     InvalidBonus,
     // Attempted to play bonus without top country
     NoTopCountry,
+    // Attempted to play a card that can only be held or discarded
+    Unplayable,
+    // Picked a card that isn't in the hand
+    InvalidIndex,
+    // Attempted to play a non-grey card as a grey card
+    NotGrey,
+    // Started a game with no players
+    NoPlayers,
+    // Started a game with more players than the deck can deal a hand to
+    NotEnoughCards { players: usize, cards: usize },
 }
 
 impl Error for BError {}
@@ -238,31 +397,38 @@ impl fmt::Display for BError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Custom(string) => write!(f, "{}", string),
-            Self::SameContinent => write!(f, "too many countries of the same continent"),
-            Self::GreyHeld => write!(f, "you can't go home with grey cards"),
-            Self::InvalidBonus => write!(f, "can't play that bonus on your top country"),
-            Self::NoTopCountry => write!(f, "you need a played country to play a bonus"),
+            Self::SameContinent => write!(f, "{}", tr("error.same_continent")),
+            Self::GreyHeld => write!(f, "{}", tr("error.grey_held")),
+            Self::InvalidBonus => write!(f, "{}", tr("error.invalid_bonus")),
+            Self::NoTopCountry => write!(f, "{}", tr("error.no_top_country")),
+            Self::Unplayable => write!(f, "{}", tr("error.unplayable")),
+            Self::InvalidIndex => write!(f, "{}", tr("error.invalid_index")),
+            Self::NotGrey => write!(f, "{}", tr("error.not_grey")),
+            Self::NoPlayers => write!(f, "{}", tr("error.no_players")),
+            Self::NotEnoughCards { players, cards } => {
+                write!(f, "{}", trf("error.not_enough_cards", &[players, cards]))
+            }
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Player {
+    name: String,
     hand: Vec<Card>,
     pile: Vec<Country>,
     score: u32,
     status: StatusHandler,
-    temp: Option<Card>,
 }
 
 impl Player {
-    fn from_hand(hand: Vec<Card>) -> Self {
+    fn from_hand(name: String, hand: Vec<Card>) -> Self {
         let mut player = Self {
+            name,
             hand,
             pile: vec![],
             score: 0,
             status: StatusHandler::empty(),
-            temp: None,
         };
         player.sort_hand();
         player
@@ -276,10 +442,15 @@ impl Player {
         self.pile.last_mut()
     }
 
+    #[allow(dead_code)]
     pub fn add_status(&mut self, status: StatusType) {
         self.status.add_status(status);
     }
 
+    fn add_hazard(&mut self, card: GreyType, status: StatusType) {
+        self.status.add_hazard(card, status);
+    }
+
     fn sort_hand(&mut self) {
         self.hand.sort();
     }
@@ -291,11 +462,9 @@ impl Player {
         Ok(())
     }
 
-    fn go_home(&mut self) -> Result<Vec<Card>, BError> {
-        self.can_go_home()?;
-
-        let to_add = self
-            .pile
+    /// Points the current pile would bank if the player went home now
+    fn trip_score(&self) -> u32 {
+        self.pile
             .iter()
             .map(|card| {
                 let Country {
@@ -306,10 +475,13 @@ impl Player {
                 } = card;
                 *score as u32 * (1 + bonus.len() as u32)
             })
-            .sum::<u32>();
+            .sum::<u32>()
+    }
+
+    fn go_home(&mut self) -> Result<Vec<Card>, BError> {
+        self.can_go_home()?;
 
-        println!("Adding {} points", to_add);
-        self.score += to_add;
+        self.score += self.trip_score();
 
         let mut cards = Vec::new();
         for mut country in self.pile.drain(..) {
@@ -354,7 +526,6 @@ impl Player {
                 self.hand.push(Card::Country(country));
                 Err(err)
             } else {
-                println!("Playing {}", &country);
                 self.pile.push(country);
                 Ok(())
             }
@@ -385,7 +556,6 @@ impl Player {
                 Err(err)
             } else {
                 let top_country = self.top_country_mut().unwrap();
-                println!("Playing {} on {}", &bonus, &top_country);
                 top_country.bonus.push(bonus);
                 Ok(())
             }
@@ -397,7 +567,7 @@ impl Player {
 
     fn play_grey(&mut self, card_index: usize) -> Result<(), BError> {
         if card_index >= self.hand.len() {
-            return Err(BError::Custom("Invalid index".to_string()));
+            return Err(BError::InvalidIndex);
         }
 
         let card = self.hand.swap_remove(card_index);
@@ -405,101 +575,279 @@ impl Player {
         if let Card::Grey(grey) = card {
             match grey {
                 GreyType::MissedFlight => {
-                    self.add_status(StatusType::MissGo(1));
-                    self.temp = Some(card);
+                    self.add_hazard(grey, StatusType::MissGo(1));
                     Ok(())
                 }
             }
         } else {
             self.hand.push(card);
-            Err(BError::Custom("Not a grey card".to_string()))
+            Err(BError::NotGrey)
         }
     }
 }
 
+#[cfg(feature = "terminal")]
 fn read_line() -> Result<String, io::Error> {
-    let mut buffer = String::new();
-    let stdin = io::stdin(); // We get `Stdin` here.
-    stdin.read_line(&mut buffer)?;
-    Ok(buffer)
-}
-
-fn get_requested_input<T, F>(message: &str, condition: F) -> T
-where
-    T: PartialOrd + std::str::FromStr<Err: std::fmt::Debug>,
-    F: Fn(&T) -> bool,
-{
-    let mut output = None;
-    while output.is_none() {
-        println!("{}", message);
-        let inp_opt = try_read!();
-
-        match inp_opt {
-            Ok(inp) => {
-                if condition(&inp) {
-                    output = Some(inp);
-                } else {
-                    println!("Invalid value\n");
-                }
-            }
-            Err(_) => println!("Error reading input\n"),
-        }
+    timer::read_line()
+}
+
+/// Optional rule variants, all off by default
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rules {
+    /// At draw time, players may take the top card of the discard pile instead of drawing from the deck,
+    /// as long as nothing has been put on the pile since their turn began
+    pub draw_from_discard: bool,
+}
+
+impl Rules {
+    /// Read rule variants from a JSON file like `{"draw_from_discard": true}`.
+    /// Any variant left out is off.
+    pub fn load(path: &std::path::Path) -> Result<Self, String> {
+        let with_path = |e: String| format!("{}: {}", path.display(), e);
+        let contents = std::fs::read_to_string(path).map_err(|e| with_path(e.to_string()))?;
+        serde_json::from_str(&contents).map_err(|e| with_path(e.to_string()))
     }
+}
 
-    output.unwrap()
+/// An action the current player can take on their turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "command", content = "index", rename_all = "snake_case")]
+pub enum Move {
+    /// Bank the points in the current pile
+    GoHome,
+    /// Play the card at this hand index
+    Play(usize),
+    /// Discard the card at this hand index
+    Discard(usize),
+}
+
+/// Something that happened in the game, seats are 0-based
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    TurnSkipped { seat: usize, remaining: u8 },
+    HazardExpired { seat: usize, card: Card },
+    CountryPlayed { seat: usize, country: Country },
+    BonusAttached { seat: usize, bonus: Bonus, country: Country },
+    HazardPlayed { seat: usize, card: GreyType },
+    Discarded { seat: usize, card: Card },
+    WentHome { seat: usize, points: u32 },
+    Drew { seat: usize, count: usize },
+    TookDiscard { seat: usize, card: Card },
+    TimedOut { seat: usize },
 }
 
 pub struct Board {
+    rules: Rules,
+    // The unshuffled deck the game was dealt from, and the seed it was shuffled with
+    deck: Deck,
+    seed: u64,
+    first_player: usize,
     future: Vec<Card>,
     past: Vec<Card>,
+    // How big `past` was when the current turn began, so a player can't take back a card
+    // they put there themselves
+    turn_discards: usize,
     players: Vec<Player>,
     turn: usize,
+    events: Vec<Event>,
+    // How many of `events` have been printed in a manual game
+    shown_events: usize,
+    // Every choice made so far, for replays
+    actions: Vec<Action>,
+    // A player quit or input ran out before the deck did
+    abandoned: bool,
+    // Limits how long each turn at the terminal can take
+    #[cfg(feature = "terminal")]
+    timer: Option<TurnTimer>,
+    // People watching the game, who are sent everything but the players' hands
+    #[cfg(feature = "terminal")]
+    spectators: Option<Spectators>,
+    // Panic as soon as a card goes missing or appears from nowhere
+    debug_audit: bool,
+}
+
+/// Copies the game, to try out moves on without changing the original. The copy has no turn
+/// timer or spectators.
+impl Clone for Board {
+    fn clone(&self) -> Self {
+        Self {
+            rules: self.rules.clone(),
+            deck: self.deck.clone(),
+            seed: self.seed,
+            first_player: self.first_player,
+            future: self.future.clone(),
+            past: self.past.clone(),
+            turn_discards: self.turn_discards,
+            players: self.players.clone(),
+            turn: self.turn,
+            events: self.events.clone(),
+            shown_events: self.shown_events,
+            actions: self.actions.clone(),
+            abandoned: self.abandoned,
+            #[cfg(feature = "terminal")]
+            timer: None,
+            #[cfg(feature = "terminal")]
+            spectators: None,
+            debug_audit: self.debug_audit,
+        }
+    }
 }
 
 impl Board {
-    pub fn new_game(num_players: usize) -> Self {
-        let mut deck = Card::deck();
+    #[cfg(feature = "terminal")]
+    pub fn new_game(num_players: usize) -> Result<Self, BError> {
+        Self::new_game_with_rules(num_players, Rules::default())
+    }
 
-        let mut rng = rand::rng();
-        deck.shuffle(&mut rng);
+    /// Start a game with a random deal. Needs the system's source of randomness, so builds
+    /// without `terminal` deal with `new_seeded_game` instead.
+    #[cfg(feature = "terminal")]
+    pub fn new_game_with_rules(num_players: usize, rules: Rules) -> Result<Self, BError> {
+        Self::new_seeded_game(num_players, rules, rand::random())
+    }
+
+    /// Start a game whose deal is fully determined by `seed`
+    pub fn new_seeded_game(num_players: usize, rules: Rules, seed: u64) -> Result<Self, BError> {
+        Self::new_game_with_deck(num_players, rules, Deck::standard(), seed)
+    }
 
-        let num_held_cards = HAND_SIZE * num_players;
+    /// Start a seeded game dealt from `deck` instead of the standard deck
+    pub fn new_game_with_deck(
+        num_players: usize,
+        rules: Rules,
+        deck: Deck,
+        seed: u64,
+    ) -> Result<Self, BError> {
+        if num_players == 0 {
+            return Err(BError::NoPlayers);
+        }
 
-        if num_held_cards >= deck.len() {
-            panic!(
-                "Too many players / Not enough cards! Players: {}, Cards: {}",
-                num_players,
-                deck.len()
-            )
+        // Too many players to even count their cards can't be dealt a hand either
+        let Some(num_held_cards) = HAND_SIZE
+            .checked_mul(num_players)
+            .filter(|&held| held < deck.len())
+        else {
+            return Err(BError::NotEnoughCards {
+                players: num_players,
+                cards: deck.len(),
+            });
+        };
+        // Odd decks are allowed, but not ones with cards the game can't handle
+        if let Err(problems) = deck.validate(num_players)
+            && problems.problems.iter().any(DeckProblem::is_fatal)
+        {
+            return Err(BError::Custom(problems.to_string()));
         }
 
-        let mut deck_iter = deck.into_iter();
+        let mut cards = deck.cards().to_vec();
+        cards.shuffle(&mut StdRng::seed_from_u64(seed));
+
+        let mut deck_iter = cards.into_iter();
         let mut to_be_held = deck_iter.by_ref().take(num_held_cards);
 
         let players: Vec<Player> = (0..num_players)
-            .map(|_| {
+            .map(|i| {
                 let hand: Vec<Card> = to_be_held.by_ref().take(HAND_SIZE).collect();
-                Player::from_hand(hand)
+                Player::from_hand(trf("player.default_name", &[&(i + 1)]), hand)
             })
             .collect();
 
         let future: Vec<Card> = deck_iter.collect();
         let past = vec![];
 
-        println!("--- Game Started ---");
-        println!("  Players: {}", players.len());
-        println!("  Hand Size: {}", HAND_SIZE);
-        println!("  Future len: {}", future.len());
-        println!();
-
-        Self {
+        let board = Self {
+            rules,
+            deck,
+            seed,
+            first_player: 0,
             future,
             past,
+            turn_discards: 0,
             players,
             turn: 0,
+            events: Vec::new(),
+            shown_events: 0,
+            actions: Vec::new(),
+            abandoned: false,
+            #[cfg(feature = "terminal")]
+            timer: None,
+            #[cfg(feature = "terminal")]
+            spectators: None,
+            debug_audit: cfg!(debug_assertions),
+        };
+        board.check_cards();
+        Ok(board)
+    }
+
+    /// Choose which seat takes the first turn
+    pub fn set_first_player(&mut self, seat: usize) {
+        self.turn = seat % self.players.len();
+        self.first_player = self.turn;
+    }
+
+    /// Name each seat in order, in place of the default "Player N"
+    pub fn set_names(&mut self, names: &[String]) {
+        for (player, name) in self.players.iter_mut().zip(names) {
+            player.name = name.clone();
         }
     }
 
+    pub fn names(&self) -> Vec<String> {
+        self.players.iter().map(|player| player.name.clone()).collect()
+    }
+
+    /// Each seat's banked score
+    pub fn scores(&self) -> Vec<u32> {
+        self.players.iter().map(|player| player.score).collect()
+    }
+
+    /// The seats with the highest score. Nobody wins if every seat ties.
+    pub fn winners(&self) -> Vec<usize> {
+        let scores = self.scores();
+        let best = scores.iter().copied().max().unwrap_or(0);
+        let winners: Vec<usize> = (0..scores.len()).filter(|&i| scores[i] == best).collect();
+
+        if winners.len() == scores.len() && scores.len() > 1 {
+            Vec::new()
+        } else {
+            winners
+        }
+    }
+
+    /// Enable or disable panicking on card conservation violations after every action.
+    /// On by default in debug builds.
+    pub fn set_debug_audit(&mut self, enabled: bool) {
+        self.debug_audit = enabled;
+    }
+
+    /// Whether the game has finished, either by running out of cards or by being abandoned
+    pub fn is_over(&self) -> bool {
+        self.future.is_empty() || self.abandoned
+    }
+
+    /// Whether the game was stopped early because a player quit or input ran out
+    pub fn is_abandoned(&self) -> bool {
+        self.abandoned
+    }
+
+    /// The seat whose turn it is
+    pub fn current_seat(&self) -> usize {
+        self.turn
+    }
+
+    /// Everything that has happened so far this game
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    fn emit(&mut self, event: Event) {
+        #[cfg(feature = "terminal")]
+        self.spectate_event(&event);
+        self.events.push(event);
+    }
+
     fn next_turn(&mut self) {
         if self.turn == self.players.len() - 1 {
             self.turn = 0;
@@ -514,86 +862,301 @@ impl Board {
 
     fn player_discard(&mut self, card_index: usize) {
         let card = self.players[self.turn].hand.swap_remove(card_index);
+        self.emit(Event::Discarded {
+            seat: self.turn,
+            card: card.clone(),
+        });
         self.discard(card);
     }
 
-    pub fn manual_game(&mut self) {
-        while !self.future.is_empty() {
-            self.turn_heading();
-            self.manual_turn();
+    fn top_discard(&self) -> Option<&Card> {
+        self.past.last()
+    }
+
+    /// Move any hazards whose status has run out on the current player to the discard pile
+    fn discard_expired_hazards(&mut self) {
+        for card in self.players[self.turn].status.cleanup() {
+            self.emit(Event::HazardExpired {
+                seat: self.turn,
+                card: card.clone(),
+            });
+            self.discard(card);
         }
     }
 
-    fn manual_turn(&mut self) {
-        if self.players[self.turn].status.no_turn() {
-            return;
+    /// Start the current player's turn, returning whether they get to act.
+    /// If they have to miss this go, their turn is passed on here.
+    pub fn begin_turn(&mut self) -> bool {
+        self.turn_discards = self.past.len();
+        let missed_go = self.players[self.turn].status.no_turn();
+        self.discard_expired_hazards();
+
+        if let Some(remaining) = missed_go {
+            self.emit(Event::TurnSkipped {
+                seat: self.turn,
+                remaining,
+            });
+            self.next_turn();
+            self.check_cards();
+            #[cfg(feature = "terminal")]
+            self.spectate_turn();
+            return false;
         }
 
-        if 1 == get_requested_input("Go home?: ", |_| true) {
-            match self.players[self.turn].go_home() {
-                Ok(mut cards) => {
-                    self.past.append(&mut cards);
-                }
-                Err(e) => {
-                    println!("{}", e);
+        true
+    }
+
+    /// Every move the current player could make without it being rejected
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let player = &self.players[self.turn];
+        let mut moves = Vec::new();
+
+        // Going home with nothing played is allowed, but it's just a wasted turn
+        if !player.pile.is_empty() && player.can_go_home().is_ok() {
+            moves.push(Move::GoHome);
+        }
+
+        for i in 0..player.hand.len() {
+            if self.can_play(i).is_ok() {
+                moves.push(Move::Play(i));
+            }
+            moves.push(Move::Discard(i));
+        }
+
+        moves
+    }
+
+    fn can_play(&self, card_index: usize) -> Result<(), BError> {
+        let player = &self.players[self.turn];
+
+        match player.hand.get(card_index) {
+            Some(Card::Country(country)) => player.can_play_country(country),
+            Some(Card::Bonus(bonus)) => player.can_play_bonus(bonus),
+            Some(Card::Grey(_)) => Ok(()),
+            Some(_) => Err(BError::Unplayable),
+            None => Err(BError::InvalidIndex),
+        }
+    }
+
+    /// Make a move for the current player. Nothing changes if the move is rejected.
+    pub fn apply_move(&mut self, mv: Move) -> Result<(), BError> {
+        let seat = self.turn;
+
+        match mv {
+            Move::GoHome => {
+                let points = self.players[seat].trip_score();
+                let mut cards = self.players[seat].go_home()?;
+                self.past.append(&mut cards);
+                self.emit(Event::WentHome { seat, points });
+            }
+            Move::Play(card_index) => {
+                self.can_play(card_index)?;
+
+                match &self.players[seat].hand[card_index] {
+                    Card::Country(country) => {
+                        let country = country.clone();
+                        self.players[seat].play_country(card_index)?;
+                        self.emit(Event::CountryPlayed { seat, country });
+                    }
+                    Card::Bonus(bonus) => {
+                        let bonus = *bonus;
+                        self.players[seat].play_bonus(card_index)?;
+                        let country = self.players[seat].top_country().unwrap().clone();
+                        self.emit(Event::BonusAttached {
+                            seat,
+                            bonus,
+                            country,
+                        });
+                    }
+                    Card::Grey(grey) => {
+                        let card = *grey;
+                        self.players[seat].play_grey(card_index)?;
+                        self.emit(Event::HazardPlayed { seat, card });
+                    }
+                    _ => return Err(BError::Unplayable),
                 }
             }
-            sleep(Duration::from_millis(PAUSE_TIME));
-        } else {
-            let mut finished_turn = false;
-            while !finished_turn {
-                sleep(Duration::from_millis(PAUSE_TIME));
-                let res = self.manual_try_turn();
-                match res {
-                    Ok(_) => finished_turn = true,
-                    Err(e) => println!("{}", e),
+            Move::Discard(card_index) => {
+                if card_index >= self.players[seat].hand.len() {
+                    return Err(BError::InvalidIndex);
                 }
+                self.player_discard(card_index);
             }
         }
 
-        self.players[self.turn].sort_hand();
-        self.next_turn();
+        self.actions.push(Action::Move(mv));
+        self.check_cards();
+        Ok(())
     }
 
-    fn manual_try_turn(&mut self) -> Result<(), BError> {
-        let mut selected =
-            get_requested_input("Pick a card to play, or 0 to discard, 10 to home", |&inp| {
-                inp <= self.players[self.turn].hand.len() || inp == 10
-            });
+    /// Whether the current player could take the top discard instead of drawing. Only a card
+    /// that was already on top when their turn began can be taken.
+    pub fn can_take_discard(&self) -> bool {
+        self.rules.draw_from_discard
+            && !self.past.is_empty()
+            && self.past.len() == self.turn_discards
+            && self.players[self.turn].hand.len() < HAND_SIZE
+    }
 
-        if selected == 0 {
-            let to_discard: usize = get_requested_input("Pick a card to discard", |&inp| {
-                inp < self.players[self.turn].hand.len() && inp > 0
+    /// Finish the current player's turn: refill their hand, from the discard pile first if
+    /// they chose to and the rules allow it, then pass the turn on
+    pub fn end_turn(&mut self, from_discard: bool) {
+        let seat = self.turn;
+        self.actions.push(Action::Draw { from_discard });
+
+        if from_discard && self.can_take_discard() {
+            let card = self.past.pop().unwrap();
+            self.emit(Event::TookDiscard {
+                seat,
+                card: card.clone(),
             });
-            let to_discard = to_discard - 1;
-            self.player_discard(to_discard);
-            return Ok(());
+            self.players[seat].hand.push(card);
         }
 
-        // Allow for 1-based indexing for the user, and for 0 to represent a discard selection
-        selected -= 1;
-        println!("Selected {}", self.players[self.turn].hand[selected]);
+        let mut count = 0;
+        while self.players[seat].hand.len() < HAND_SIZE {
+            match self.future.pop() {
+                Some(card) => self.players[seat].hand.push(card),
+                None => break,
+            }
+            count += 1;
+        }
+        if count > 0 {
+            self.emit(Event::Drew { seat, count });
+        }
 
-        let out = match &self.players[self.turn].hand[selected] {
-            Card::Bonus(_) => self.manual_play_bonus(selected),
-            Card::Country(_) => self.manual_play_country(selected),
-            Card::Grey(_) => self.manual_play_grey(selected),
-            _ => Ok(()),
-        };
-        sleep(Duration::from_millis(PAUSE_TIME));
+        self.players[seat].sort_hand();
+        self.next_turn();
+        self.check_cards();
+        #[cfg(feature = "terminal")]
+        self.spectate_turn();
+    }
+}
 
-        out
+#[cfg(feature = "terminal")]
+impl Board {
+    /// End the game early because a player left or input ran out, letting anyone watching
+    /// know it's over
+    fn abandon(&mut self) {
+        self.abandoned = true;
+        self.spectate_turn();
     }
 
-    fn manual_play_bonus(&mut self, card_index: usize) -> Result<(), BError> {
-        self.players[self.turn].play_bonus(card_index)
+    /// Print any events that haven't been shown yet
+    fn show_events(&mut self) {
+        for event in &self.events[self.shown_events..] {
+            println!("{}", event);
+        }
+        self.shown_events = self.events.len();
     }
 
-    fn manual_play_country(&mut self, card_index: usize) -> Result<(), BError> {
-        self.players[self.turn].play_country(card_index)
+    /// Ask the current player whether to take the top discard, if the rules allow it
+    fn manual_draw_choice(&mut self) -> Option<bool> {
+        if !self.can_take_discard() {
+            return Some(false);
+        }
+
+        let message = trf("prompt.draw_choice", &[self.top_discard().unwrap()]);
+        self.ask(&message, |_, input| match input.trim() {
+            "1" => Ok(false),
+            "2" => Ok(true),
+            _ => Err(tr("prompt.invalid_value").to_string()),
+        })
     }
 
-    fn manual_play_grey(&mut self, card_index: usize) -> Result<(), BError> {
-        self.players[self.turn].play_grey(card_index)
+    pub fn manual_game(&mut self) {
+        let mut bots: Vec<Option<Box<dyn Strategy>>> = self.players.iter().map(|_| None).collect();
+        self.play_game(&mut bots);
+    }
+
+    /// Play the rest of the game with a bot in each seat that has one, and people taking
+    /// turns at the terminal in the others
+    pub fn play_game(&mut self, bots: &mut [Option<Box<dyn Strategy>>]) {
+        assert_eq!(bots.len(), self.players.len(), "Need one entry per seat");
+
+        if OutputFormat::current() == OutputFormat::Jsonl {
+            return self.jsonl_game(bots);
+        }
+
+        self.game_heading();
+        while !self.is_over() {
+            match bots[self.turn].as_deref_mut() {
+                Some(bot) => {
+                    println!();
+                    println!("{}", trf("heading.turn", &[&self.players[self.turn].name]));
+                    self.bot_turn(bot);
+                    self.show_events();
+                    pause();
+                }
+                None => {
+                    self.turn_heading();
+                    self.manual_turn();
+                }
+            }
+        }
+        self.show_scores();
+    }
+
+    fn manual_turn(&mut self) {
+        if !self.begin_turn() {
+            self.show_events();
+            pause();
+            return;
+        }
+        self.show_events();
+        if let Some(timer) = &mut self.timer {
+            timer.start();
+        }
+
+        // A prompt left unanswered without the game being abandoned means the time ran out
+        loop {
+            match self.manual_try_turn() {
+                None if self.abandoned => return,
+                None => return self.manual_time_out(),
+                Some(Ok(_)) => break,
+                Some(Err(e)) => println!("{}", e),
+            }
+        }
+
+        let from_discard = match self.manual_draw_choice() {
+            Some(from_discard) => from_discard,
+            None if self.abandoned => return,
+            None => return self.manual_time_out(),
+        };
+        self.end_turn(from_discard);
+        self.show_events();
+    }
+
+    fn manual_time_out(&mut self) {
+        self.time_out();
+        self.show_events();
+        pause();
+    }
+
+    /// Ask for and make one move, or `None` if the player left the game or ran out of time
+    fn manual_try_turn(&mut self) -> Option<Result<(), BError>> {
+        let command = self.ask(tr("prompt.pick_card"), |board, input| {
+            parse_command(input, &board.players[board.turn].hand)
+        })?;
+        let mv = match command {
+            Command::Move(mv) => mv,
+            Command::PickDiscard => {
+                let index = self.ask(tr("prompt.pick_discard"), |board, input| {
+                    find_card(input, &board.players[board.turn].hand, Kind::Any)
+                })?;
+                Move::Discard(index)
+            }
+        };
+
+        if let Move::Play(selected) = mv {
+            let card: &Card = &self.players[self.turn].hand[selected];
+            println!("{}", trf("prompt.selected", &[card]));
+        }
+
+        let out = self.apply_move(mv);
+        self.show_events();
+        pause();
+
+        Some(out)
     }
 }
@@ -0,0 +1,470 @@
+use std::{
+    fmt::Display,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// A language that game text can be shown in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    English,
+    Spanish,
+}
+
+static LOCALE: AtomicU8 = AtomicU8::new(Locale::English as u8);
+
+impl Locale {
+    /// Parse a language code such as `en`, `es` or `es_ES.UTF-8`
+    pub fn parse(code: &str) -> Option<Self> {
+        let language = code.split(['_', '-', '.']).next()?.to_lowercase();
+        match language.as_str() {
+            "en" => Some(Self::English),
+            "es" => Some(Self::Spanish),
+            _ => None,
+        }
+    }
+
+    /// The locale all game text is currently shown in
+    pub fn current() -> Self {
+        match LOCALE.load(Ordering::Relaxed) {
+            1 => Self::Spanish,
+            _ => Self::English,
+        }
+    }
+
+    /// Show all game text in this locale from now on
+    pub fn set(self) {
+        LOCALE.store(self as u8, Ordering::Relaxed);
+    }
+
+    fn catalogue(self) -> fn(&str) -> Option<&'static str> {
+        match self {
+            Self::English => english,
+            Self::Spanish => spanish,
+        }
+    }
+
+    /// Look up a message in this locale, falling back to English
+    fn lookup(self, key: &str) -> Option<&'static str> {
+        self.catalogue()(key).or_else(|| english(key))
+    }
+}
+
+/// Whether `name` is the default name of `seat` (1-based) in any locale
+#[cfg(feature = "terminal")]
+pub(crate) fn is_default_name(name: &str, seat: usize) -> bool {
+    [Locale::English, Locale::Spanish]
+        .into_iter()
+        .filter_map(|locale| locale.catalogue()("player.default_name"))
+        .any(|default| default.replace("{0}", &seat.to_string()) == name)
+}
+
+/// Look up a message in the current locale, falling back to English
+pub(crate) fn lookup(key: &str) -> Option<&'static str> {
+    Locale::current().lookup(key)
+}
+
+/// Look up a message that is always in the catalogue
+pub(crate) fn tr(key: &'static str) -> &'static str {
+    lookup(key).unwrap_or(key)
+}
+
+/// Look up a message and fill its `{0}`, `{1}`, ... placeholders with `args`. The template is
+/// read once, so braces inside an argument are left alone.
+pub(crate) fn trf(key: &'static str, args: &[&dyn Display]) -> String {
+    let mut rest = tr(key);
+    let mut message = String::with_capacity(rest.len());
+
+    while let Some(start) = rest.find('{') {
+        message.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let arg = rest
+            .find('}')
+            .and_then(|end| Some((rest[1..end].parse::<usize>().ok()?, end)))
+            .and_then(|(i, end)| Some((args.get(i)?, end)));
+        match arg {
+            Some((arg, end)) => {
+                message.push_str(&arg.to_string());
+                rest = &rest[end + 1..];
+            }
+            None => {
+                message.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    message.push_str(rest);
+    message
+}
+
+fn english(key: &str) -> Option<&'static str> {
+    Some(match key {
+        // Continents
+        "continent.africa" => "Africa",
+        "continent.america" => "America",
+        "continent.antarctica" => "Antarctica",
+        "continent.asia" => "Asia",
+        "continent.europe" => "Europe",
+        "continent.oceania" => "Oceania",
+
+        // Cards. Country names are used as they are in the deck unless translated.
+        "bonus.beach" => "Beach Bonus",
+        "bonus.culture" => "Culture Bonus",
+        "bonus.trekking" => "Trekking Bonus",
+        "bonus.wildlife" => "Wildlife Bonus",
+        "advice.money" => "Money Talks",
+        "advice.format" => "{0} {1}",
+        "advice.good.bureaucracy" | "advice.good.timing" | "advice.good.transport" => "Good",
+        "advice.bad.bureaucracy" | "advice.bad.timing" | "advice.bad.transport" => "Bad",
+        "advice.bureaucracy" => "Bureaucracy",
+        "advice.timing" => "Timing",
+        "advice.transport" => "Transport",
+        "grey.missed_flight" => "Missed Flight",
+        "special.credit_card" => "Credit Card",
+        "hazard.one_go" => "{0} - 1 go left to miss",
+        "hazard.gos" => "{0} - {1} goes left to miss",
+
+        // Errors
+        "error.same_continent" => "too many countries of the same continent",
+        "error.grey_held" => "you can't go home with grey cards",
+        "error.invalid_bonus" => "can't play that bonus on your top country",
+        "error.no_top_country" => "you need a played country to play a bonus",
+        "error.unplayable" => "that card can't be played",
+        "error.invalid_index" => "Invalid index",
+        "error.not_grey" => "Not a grey card",
+        "error.no_players" => "A game needs at least one player",
+        "error.not_enough_cards" => "Not enough cards for {0} players, the deck only has {1}",
+
+        // Prompts
+        "prompt.invalid_value" => "Invalid value",
+        "prompt.read_error" => "Error reading input",
+        "prompt.pick_card" => "Your move: play <card>, bonus <type>, grey <card>, discard <card> or home. Cards can be named or numbered",
+        "prompt.pick_discard" => "Pick a card to discard",
+        "prompt.selected" => "Selected {0}",
+        "prompt.draw_choice" => "Draw from the deck (1) or take {0} from the discard pile (2)?",
+        "prompt.known_players" => "Known players:",
+        "prompt.who_is" => "Who is player {0}? Pick a number or type a new name",
+        "prompt.already_playing" => "{0} is already playing\n",
+
+        // Turn commands
+        "command.play" => "play",
+        "command.bonus" => "bonus",
+        "command.grey" => "grey",
+        "command.discard" => "discard",
+        "command.home" => "home",
+        "command.kind.card" => "card",
+        "command.kind.bonus" => "bonus card",
+        "command.kind.grey" => "grey card",
+        "command.no_number" => "There's no card {0} in your hand",
+        "command.not_kind" => "{0} isn't a {1}",
+        "command.none_of_kind" => "You have no {0} to play",
+        "command.which" => "Which card? Give its name or number",
+        "command.unknown_card" => "You don't have a card called `{0}`",
+        "command.ambiguous" => "`{0}` could be {1}",
+
+        // Meta commands
+        "meta.help" => "help",
+        "meta.scores" => "scores",
+        "meta.piles" => "piles",
+        "meta.discards" => "discards",
+        "meta.status" => "status",
+        "meta.risk" => "risk",
+        "meta.save" => "save",
+        "meta.quit" => "quit",
+        "meta.help_text" => "On your turn:
+| play <card>      play a card, or just type its name or number
+| bonus [type]     play a bonus card on your top country
+| grey [card]      play a grey card on yourself to clear it from your hand
+| discard <card>   discard a card
+| home             go home and bank your pile
+At any prompt:
+| help             show this help
+| scores           everyone's banked score and pile score
+| piles            everyone's pile
+| discards         the whole discard pile
+| status           the deck, the discard pile and your hazards
+| risk             the odds of going home now or playing on
+| save [path]      save the game so far, to game.json if no path is given
+| quit             stop playing
+Rules:
+| Make one move a turn, then draw back up to 5 cards.
+| Your pile can have one country from each continent, or two with a Credit Card in hand.
+| A bonus goes on your top country if it allows it, scoring that country again.
+| Going home banks your pile, but not while you hold a grey card.
+| Missed Flight makes you miss your next go. Advice can only be discarded.
+| The game ends when the deck runs out, and the highest score wins.",
+        "meta.quitting" => "Leaving the game",
+        "meta.score_line" => "{0}: {1} banked, {2} on the pile",
+        "meta.deck_left" => "{0} cards left in the deck",
+        "meta.discard_size" => "{0} cards in the discard pile",
+        "meta.no_hazards" => "No hazards",
+        "meta.saved" => "Game saved to {0}",
+        "meta.save_failed" => "Couldn't save to {0}: {1}",
+
+        // Going home odds
+        "risk.home_now" => "Going home now banks {0} points",
+        "risk.grey_held" => "You can't go home now, you're holding a grey card",
+        "risk.play_on" => "Playing on and going home as soon as you can is worth {0} points on average",
+        "risk.turns_left" => "About {0} more turns before the deck runs out",
+        "risk.draws" => "Your next draw of {0} comes from {1} cards you haven't seen:",
+        "risk.missed_flight" => "{0} chance of a Missed Flight",
+        "risk.useful_bonus" => "{0} chance of a bonus for your top country",
+        "risk.most_likely" => "Most likely cards:",
+        "risk.go_home" => "Going home now is the safer bet",
+        "risk.keep_going" => "Playing on is worth the risk",
+
+        // Turn timer
+        "timer.left" => "{0}s left",
+
+        // Events
+        "event.missed_go" => "Missing this go.",
+        "event.missed_gos" => "Missing this go. {0} more to go",
+        "event.hazard_expired" => "{0} is over",
+        "event.played" => "Playing {0}",
+        "event.bonus_attached" => "Playing {0} on {1}",
+        "event.discarded" => "Discarding {0}",
+        "event.went_home" => "Adding {0} points",
+        "event.drew_one" => "Drew a card",
+        "event.drew" => "Drew {0} cards",
+        "event.took_discard" => "Took {0}",
+        "event.timed_out" => "Out of time, so the turn is played for you",
+
+        // Headings
+        "heading.game_started" => "--- Game Started ---",
+        "heading.players" => "  Players: {0}",
+        "heading.hand_size" => "  Hand Size: {0}",
+        "heading.future" => "  Future len: {0}",
+        "heading.turn" => "Its {0}'s turn",
+        "heading.hand" => "{0}'s hand:",
+        "heading.hazards" => "Active hazards:",
+        "heading.discard" => "Discard pile: {0}",
+        "heading.discard_empty" => "Discard pile: empty",
+        "heading.pile" => "{0}: {1} points",
+        "heading.final_scores" => "--- Final Scores ---",
+        "tracker.heading" => "Cards you haven't seen ({0}):",
+        "tracker.bonus" => "Bonuses",
+        "tracker.advice" => "Advice",
+        "tracker.grey" => "Grey cards",
+        "tracker.special" => "Special cards",
+        "player.default_name" => "Player {0}",
+        "profile.summary" => "{0} - {1} games, {2} wins, {3} average trip",
+        "profile.favourite" => ", loves {0}",
+
+        // Matches
+        "match.game" => "=== Game {0} of {1} ===",
+        "match.standings" => "--- Standings after {0} of {1} games ---",
+        "match.game_column" => "Game {0}",
+        "match.total" => "Total",
+
+        _ => return None,
+    })
+}
+
+fn spanish(key: &str) -> Option<&'static str> {
+    Some(match key {
+        "continent.africa" => "África",
+        "continent.america" => "América",
+        "continent.antarctica" => "Antártida",
+        "continent.asia" => "Asia",
+        "continent.europe" => "Europa",
+        "continent.oceania" => "Oceanía",
+
+        "country.Mali" => "Malí",
+        "country.Egypt" => "Egipto",
+        "country.Kenya" => "Kenia",
+        "country.Morocco" => "Marruecos",
+        "country.South Africa" => "Sudáfrica",
+        "country.Zimbabwe" => "Zimbabue",
+        "country.Brazil" => "Brasil",
+        "country.Peru" => "Perú",
+        "country.Mexico" => "México",
+        "country.USA" => "EE. UU.",
+        "country.Canada" => "Canadá",
+        "country.Antarctica" => "Antártida",
+        "country.Uzbekistan" => "Uzbekistán",
+        "country.Thailand" => "Tailandia",
+        "country.Japan" => "Japón",
+        "country.Russia" => "Rusia",
+        "country.Turkey" => "Turquía",
+        "country.Italy" => "Italia",
+        "country.Germany" => "Alemania",
+        "country.Ireland" => "Irlanda",
+        "country.UK" => "Reino Unido",
+        "country.France" => "Francia",
+        "country.Holland" => "Holanda",
+        "country.Easter Island" => "Isla de Pascua",
+        "country.Tahiti" => "Tahití",
+        "country.New Zealand" => "Nueva Zelanda",
+        "country.Cook Islands" => "Islas Cook",
+        "country.Fiji" => "Fiyi",
+
+        "bonus.beach" => "Bonus de playa",
+        "bonus.culture" => "Bonus de cultura",
+        "bonus.trekking" => "Bonus de senderismo",
+        "bonus.wildlife" => "Bonus de fauna",
+        "advice.money" => "El dinero manda",
+        "advice.good.bureaucracy" => "Buena",
+        "advice.bad.bureaucracy" => "Mala",
+        "advice.good.timing" | "advice.good.transport" => "Buen",
+        "advice.bad.timing" | "advice.bad.transport" => "Mal",
+        "advice.bureaucracy" => "burocracia",
+        "advice.timing" => "momento",
+        "advice.transport" => "transporte",
+        "grey.missed_flight" => "Vuelo perdido",
+        "special.credit_card" => "Tarjeta de crédito",
+        "hazard.one_go" => "{0} - queda 1 turno por perder",
+        "hazard.gos" => "{0} - quedan {1} turnos por perder",
+
+        "error.same_continent" => "demasiados países del mismo continente",
+        "error.grey_held" => "no puedes volver a casa con cartas grises",
+        "error.invalid_bonus" => "no puedes jugar ese bonus en tu último país",
+        "error.no_top_country" => "necesitas un país jugado para jugar un bonus",
+        "error.unplayable" => "esa carta no se puede jugar",
+        "error.invalid_index" => "Índice no válido",
+        "error.not_grey" => "No es una carta gris",
+        "error.no_players" => "Una partida necesita al menos un jugador",
+        "error.not_enough_cards" => "No hay cartas suficientes para {0} jugadores, el mazo solo tiene {1}",
+
+        "prompt.invalid_value" => "Valor no válido",
+        "prompt.read_error" => "Error al leer la entrada",
+        "prompt.pick_card" => "Tu jugada: jugar <carta>, bonus <tipo>, gris <carta>, descartar <carta> o casa. Las cartas se pueden nombrar o numerar",
+        "prompt.pick_discard" => "Elige una carta para descartar",
+        "prompt.selected" => "Has elegido {0}",
+        "prompt.draw_choice" => "¿Robar del mazo (1) o coger {0} de la pila de descartes (2)?",
+        "prompt.known_players" => "Jugadores conocidos:",
+        "prompt.who_is" => "¿Quién es el jugador {0}? Elige un número o escribe un nombre nuevo",
+        "prompt.already_playing" => "{0} ya está jugando\n",
+
+        "command.play" => "jugar",
+        "command.bonus" => "bonus",
+        "command.grey" => "gris",
+        "command.discard" => "descartar",
+        "command.home" => "casa",
+        "command.kind.card" => "carta",
+        "command.kind.bonus" => "carta de bonus",
+        "command.kind.grey" => "carta gris",
+        "command.no_number" => "No tienes ninguna carta {0} en la mano",
+        "command.not_kind" => "{0} no es una {1}",
+        "command.none_of_kind" => "No tienes ninguna {0} para jugar",
+        "command.which" => "¿Qué carta? Di su nombre o su número",
+        "command.unknown_card" => "No tienes ninguna carta llamada `{0}`",
+        "command.ambiguous" => "`{0}` puede ser {1}",
+
+        "meta.help" => "ayuda",
+        "meta.scores" => "puntos",
+        "meta.piles" => "pilas",
+        "meta.discards" => "descartes",
+        "meta.status" => "estado",
+        "meta.risk" => "riesgo",
+        "meta.save" => "guardar",
+        "meta.quit" => "salir",
+        "meta.help_text" => "En tu turno:
+| jugar <carta>     juega una carta, o escribe solo su nombre o número
+| bonus [tipo]      juega un bonus sobre tu país de arriba
+| gris [carta]      juega una carta gris sobre ti para quitarla de tu mano
+| descartar <carta> descarta una carta
+| casa              vuelve a casa y guarda tu pila
+En cualquier momento:
+| ayuda             muestra esta ayuda
+| puntos            los puntos guardados y de la pila de cada uno
+| pilas             la pila de cada uno
+| descartes         toda la pila de descartes
+| estado            el mazo, los descartes y tus peligros
+| riesgo            las probabilidades de volver a casa ahora o seguir jugando
+| guardar [ruta]    guarda la partida, en game.json si no das ruta
+| salir             deja de jugar
+Reglas:
+| Haz un movimiento por turno y luego roba hasta tener 5 cartas.
+| Tu pila puede tener un país de cada continente, o dos con una Tarjeta de crédito en la mano.
+| Un bonus va sobre tu país de arriba si lo permite, y suma ese país otra vez.
+| Volver a casa guarda tu pila, pero no mientras tengas una carta gris.
+| Vuelo perdido te hace perder el siguiente turno. Los consejos solo se pueden descartar.
+| La partida acaba cuando se termina el mazo, y gana la puntuación más alta.",
+        "meta.quitting" => "Dejas la partida",
+        "meta.score_line" => "{0}: {1} guardados, {2} en la pila",
+        "meta.deck_left" => "Quedan {0} cartas en el mazo",
+        "meta.discard_size" => "{0} cartas en la pila de descartes",
+        "meta.no_hazards" => "Sin peligros",
+        "meta.saved" => "Partida guardada en {0}",
+        "meta.save_failed" => "No se pudo guardar en {0}: {1}",
+
+        "risk.home_now" => "Volver a casa ahora suma {0} puntos",
+        "risk.grey_held" => "No puedes volver a casa ahora, tienes una carta gris",
+        "risk.play_on" => "Seguir jugando y volver a casa en cuanto puedas vale {0} puntos de media",
+        "risk.turns_left" => "Quedan unos {0} turnos antes de que se acabe el mazo",
+        "risk.draws" => "Tu próximo robo de {0} sale de {1} cartas que no has visto:",
+        "risk.missed_flight" => "{0} de probabilidad de un Vuelo perdido",
+        "risk.useful_bonus" => "{0} de probabilidad de un bonus para tu país de arriba",
+        "risk.most_likely" => "Cartas más probables:",
+        "risk.go_home" => "Volver a casa ahora es lo más seguro",
+        "risk.keep_going" => "Merece la pena seguir jugando",
+
+        "timer.left" => "quedan {0} s",
+
+        "event.missed_go" => "Pierdes este turno.",
+        "event.missed_gos" => "Pierdes este turno. Faltan {0} más",
+        "event.hazard_expired" => "{0} ha terminado",
+        "event.played" => "Juegas {0}",
+        "event.bonus_attached" => "Juegas {0} en {1}",
+        "event.discarded" => "Descartas {0}",
+        "event.went_home" => "Sumas {0} puntos",
+        "event.drew_one" => "Robas una carta",
+        "event.drew" => "Robas {0} cartas",
+        "event.took_discard" => "Coges {0}",
+        "event.timed_out" => "Se acabó el tiempo, el turno se juega por ti",
+
+        "heading.game_started" => "--- Empieza la partida ---",
+        "heading.players" => "  Jugadores: {0}",
+        "heading.hand_size" => "  Tamaño de mano: {0}",
+        "heading.future" => "  Cartas en el mazo: {0}",
+        "heading.turn" => "Turno de {0}",
+        "heading.hand" => "Mano de {0}:",
+        "heading.hazards" => "Peligros activos:",
+        "heading.discard" => "Pila de descartes: {0}",
+        "heading.discard_empty" => "Pila de descartes: vacía",
+        "heading.pile" => "{0}: {1} puntos",
+        "heading.final_scores" => "--- Puntuación final ---",
+        "tracker.heading" => "Cartas que no has visto ({0}):",
+        "tracker.bonus" => "Bonus",
+        "tracker.advice" => "Consejos",
+        "tracker.grey" => "Cartas grises",
+        "tracker.special" => "Cartas especiales",
+        "player.default_name" => "Jugador {0}",
+        "profile.summary" => "{0} - {1} partidas, {2} victorias, {3} de media por viaje",
+        "profile.favourite" => ", le encanta {0}",
+
+        "match.game" => "=== Partida {0} de {1} ===",
+        "match.standings" => "--- Clasificación tras {0} de {1} partidas ---",
+        "match.game_column" => "Partida {0}",
+        "match.total" => "Total",
+
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn braces_in_an_argument_are_left_alone() {
+        assert_eq!(
+            trf("command.ambiguous", &[&"{1}", &"Mali"]),
+            "`{1}` could be Mali"
+        );
+        assert_eq!(trf("advice.format", &[&"{0}", &"{1}"]), "{0} {1}");
+    }
+
+    #[test]
+    fn placeholders_without_an_argument_are_left_alone() {
+        assert_eq!(trf("advice.format", &[&"Good"]), "Good {1}");
+    }
+
+    #[test]
+    fn a_key_missing_from_a_locale_falls_back_to_english() {
+        assert_eq!(spanish("advice.format"), None);
+        assert_eq!(Locale::Spanish.lookup("advice.format"), Some("{0} {1}"));
+        assert_eq!(Locale::Spanish.lookup("meta.help"), Some("ayuda"));
+        assert_eq!(Locale::Spanish.lookup("no.such.key"), None);
+    }
+}
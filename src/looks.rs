@@ -1,105 +1,120 @@
 use std::fmt::Display;
 
-use colored::{Colorize, CustomColor};
+use crate::{
+    Advice, AdviceType, Board, Bonus, Card, CardStyle, Continent, Country, Event, GreyType,
+    HAND_SIZE, Hazard, Player, Special, StatusType,
+    locale::{lookup, tr, trf},
+    style::{self, Theme},
+    tracker,
+};
 
-use crate::{Advice, AdviceType, Board, Bonus, Card, Continent, Country, GreyType, Player, Special};
+impl Country {
+    /// The country's name in the current locale
+    pub(crate) fn local_name(&self) -> &str {
+        lookup(&format!("country.{}", self.name)).unwrap_or(&self.name)
+    }
+}
 
 impl Display for Country {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.continent() {
-            Continent::Africa => write!(
-                f,
-                "{}",
-                self.name.custom_color(CustomColor::new(134, 80, 29))
-            ),
-            Continent::Asia => write!(
-                f,
-                "{}",
-                self.name.custom_color(CustomColor::new(196, 181, 61))
-            ),
-            Continent::America => write!(
-                f,
-                "{}",
-                self.name.custom_color(CustomColor::new(234, 83, 119))
-            ),
-            Continent::Antarctica => write!(
-                f,
-                "{}",
-                self.name.custom_color(CustomColor::new(220, 220, 220))
-            ),
-            Continent::Europe => write!(
-                f,
-                "{}",
-                self.name.custom_color(CustomColor::new(118, 72, 141))
-            ),
-            Continent::Oceania => write!(
-                f,
-                "{}",
-                self.name.custom_color(CustomColor::new(113, 209, 164))
-            ),
+        let continent = self.continent();
+        let paint = Theme::current().continent(continent);
+
+        write!(f, "{}", paint.paint(self.local_name()))?;
+        if style::tags() {
+            write!(f, " [{}]", continent.tag())?;
+        }
+        Ok(())
+    }
+}
+
+impl Continent {
+    /// Short label shown next to countries when colour alone isn't enough
+    fn tag(&self) -> &'static str {
+        match self {
+            Continent::Africa => "AF",
+            Continent::America => "AM",
+            Continent::Antarctica => "AN",
+            Continent::Asia => "AS",
+            Continent::Europe => "EU",
+            Continent::Oceania => "OC",
+        }
+    }
+}
+
+impl Display for Continent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Continent::Africa => write!(f, "{}", tr("continent.africa")),
+            Continent::America => write!(f, "{}", tr("continent.america")),
+            Continent::Antarctica => write!(f, "{}", tr("continent.antarctica")),
+            Continent::Asia => write!(f, "{}", tr("continent.asia")),
+            Continent::Europe => write!(f, "{}", tr("continent.europe")),
+            Continent::Oceania => write!(f, "{}", tr("continent.oceania")),
         }
     }
 }
 
 impl Display for Bonus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let color = CustomColor::new(106, 229, 218);
+        let paint = Theme::current().bonus;
 
         match self {
-            Bonus::Beach => write!(f, "{}", "Beach Bonus".custom_color(color)),
-            Bonus::Culture => write!(f, "{}", "Culture Bonus".custom_color(color)),
-            Bonus::Trekking => write!(f, "{}", "Trekking Bonus".custom_color(color)),
-            Bonus::Wildlife => write!(f, "{}", "Wildlife Bonus".custom_color(color)),
+            Bonus::Beach => write!(f, "{}", paint.paint(tr("bonus.beach"))),
+            Bonus::Culture => write!(f, "{}", paint.paint(tr("bonus.culture"))),
+            Bonus::Trekking => write!(f, "{}", paint.paint(tr("bonus.trekking"))),
+            Bonus::Wildlife => write!(f, "{}", paint.paint(tr("bonus.wildlife"))),
+        }
+    }
+}
+
+impl Advice {
+    /// Catalogue keys for the good/bad word and the name of non-money advice
+    pub(crate) fn keys(&self) -> (&'static str, &'static str) {
+        match (self.good, self.variant) {
+            (true, AdviceType::Bureaucracy) => ("advice.good.bureaucracy", "advice.bureaucracy"),
+            (false, AdviceType::Bureaucracy) => ("advice.bad.bureaucracy", "advice.bureaucracy"),
+            (true, AdviceType::Timing) => ("advice.good.timing", "advice.timing"),
+            (false, AdviceType::Timing) => ("advice.bad.timing", "advice.timing"),
+            (true, AdviceType::Transport) => ("advice.good.transport", "advice.transport"),
+            (false, AdviceType::Transport) => ("advice.bad.transport", "advice.transport"),
+            _ => panic!("Wont happen"),
         }
     }
 }
 
 impl Display for Advice {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let theme = Theme::current();
+
+        if style::tags() {
+            write!(f, "{} ", if self.good { "[+]" } else { "[-]" })?;
+        }
+
         if self.variant == AdviceType::Money {
-            return write!(f, "{}", "Money Talks".green());
+            return write!(f, "{}", theme.money.paint(tr("advice.money")));
         }
 
-        let pre = if self.good { "Good" } else { "Bad" };
-        let (c1, c2) = if self.good {
-            (
-                CustomColor::new(12, 186, 74),
-                CustomColor::new(81, 255, 143),
-            )
-        } else {
-            (
-                CustomColor::new(248, 30, 88),
-                CustomColor::new(248, 73, 119),
-            )
-        };
+        let (c1, c2) = if self.good { theme.good } else { theme.bad };
+        let (pre, name) = self.keys();
 
-        match self.variant {
-            AdviceType::Bureaucracy => write!(
-                f,
-                "{} {}",
-                pre.custom_color(c1),
-                "Bureaucracy".custom_color(c2)
-            ),
-            AdviceType::Timing => {
-                write!(f, "{} {}", pre.custom_color(c1), "Timing".custom_color(c2))
-            }
-            AdviceType::Transport => write!(
-                f,
-                "{} {}",
-                pre.custom_color(c1),
-                "Transport".custom_color(c2)
-            ),
-            _ => panic!("Wont happen"),
-        }
+        write!(
+            f,
+            "{}",
+            trf(
+                "advice.format",
+                &[&c1.paint(tr(pre)), &c2.paint(tr(name))]
+            )
+        )
     }
 }
 
 impl Display for GreyType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let grey = CustomColor::new(120, 120, 120);
+        let grey = Theme::current().grey;
 
         match self {
-            GreyType::MissedFlight => write!(f, "{}", "Missed Flight".custom_color(grey)),
+            GreyType::MissedFlight => write!(f, "{}", grey.paint(tr("grey.missed_flight"))),
         }
     }
 }
@@ -107,11 +122,66 @@ impl Display for GreyType {
 impl Display for Special {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::CerditCard => write!(f, "{}", "Credit Card".custom_color(CustomColor::new(231, 157, 72))),
+            Self::CerditCard => write!(
+                f,
+                "{}",
+                Theme::current().special.paint(tr("special.credit_card"))
+            ),
+        }
+    }
+}
+
+impl Display for Hazard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.status {
+            StatusType::MissGo(1) => write!(f, "{}", trf("hazard.one_go", &[&self.card])),
+            StatusType::MissGo(gos) => write!(f, "{}", trf("hazard.gos", &[&self.card, &gos])),
+            _ => write!(f, "{}", self.card),
         }
     }
 }
 
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            Event::TurnSkipped { remaining: 0, .. } => tr("event.missed_go").to_string(),
+            Event::TurnSkipped { remaining, .. } => trf("event.missed_gos", &[remaining]),
+            Event::HazardExpired { card, .. } => trf("event.hazard_expired", &[card]),
+            Event::CountryPlayed { country, .. } => trf("event.played", &[country]),
+            Event::BonusAttached { bonus, country, .. } => {
+                trf("event.bonus_attached", &[bonus, country])
+            }
+            Event::HazardPlayed { card, .. } => trf("event.played", &[card]),
+            Event::Discarded { card, .. } => trf("event.discarded", &[card]),
+            Event::WentHome { points, .. } => trf("event.went_home", &[points]),
+            Event::Drew { count: 1, .. } => tr("event.drew_one").to_string(),
+            Event::Drew { count, .. } => trf("event.drew", &[count]),
+            Event::TookDiscard { card, .. } => trf("event.took_discard", &[card]),
+            Event::TimedOut { .. } => tr("event.timed_out").to_string(),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+#[cfg(feature = "terminal")]
+impl Display for crate::Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let average = format!("{:.1}", self.average_trip_score());
+        write!(
+            f,
+            "{}",
+            trf(
+                "profile.summary",
+                &[&self.name, &self.games, &self.wins, &average]
+            )
+        )?;
+        if let Some(continent) = self.favourite_continent() {
+            write!(f, "{}", trf("profile.favourite", &[&continent]))?;
+        }
+        Ok(())
+    }
+}
+
 impl Display for Card {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -125,6 +195,7 @@ impl Display for Card {
 }
 
 impl Player {
+    #[allow(dead_code)]
     fn show_pile(&self) {
         print!("[");
         for (i, c) in self.pile.iter().enumerate() {
@@ -136,6 +207,7 @@ impl Player {
         println!("]");
     }
 
+    #[allow(dead_code)]
     fn try_playing_all_counties(&mut self) {
         for i in 0..self.hand.len() {
             let _ = self.play_country(i);
@@ -143,6 +215,7 @@ impl Player {
         }
     }
 
+    #[allow(dead_code)]
     fn try_playing_all_bonuses(&mut self) {
         for i in 0..self.hand.len() {
             let _ = self.play_bonus(i);
@@ -242,42 +315,55 @@ impl Card {
 // use tabular::{Table, Row};
 
 impl Board {
+    pub fn game_heading(&self) {
+        println!("{}", tr("heading.game_started"));
+        println!("{}", trf("heading.players", &[&self.players.len()]));
+        println!("{}", trf("heading.hand_size", &[&HAND_SIZE]));
+        println!("{}", trf("heading.future", &[&self.future.len()]));
+        println!();
+    }
+
     pub fn turn_heading(&self) {
         println!();
         println!("--------------------------");
-        println!("Its player {}'s turn", self.turn + 1);
+        println!("{}", trf("heading.turn", &[&self.players[self.turn].name]));
 
         // Current player's hand
-        println!("Player {}'s hand:", self.turn + 1);
-        self.players[self.turn]
-            .hand
-            .iter()
-            .enumerate()
-            .for_each(|(i, card)| println!("| {} {}", i + 1, card));
+        println!("{}", trf("heading.hand", &[&self.players[self.turn].name]));
+        let card_style = CardStyle::current();
+        if card_style == CardStyle::Compact {
+            self.players[self.turn]
+                .hand
+                .iter()
+                .enumerate()
+                .for_each(|(i, card)| println!("| {} {}", i + 1, card));
+        } else {
+            self.show_boxed_hand(card_style);
+        }
 
-        println!();
+        // Current player's active hazards
+        let hazards = &self.players[self.turn].status.hazards;
+        if !hazards.is_empty() {
+            println!("{}", tr("heading.hazards"));
+            hazards.iter().for_each(|hazard| println!("| {}", hazard));
+        }
 
-        // All player's played piles
-        for (i, player) in self.players.iter().enumerate() {
-            for (j, card) in player.pile.iter().enumerate() {
-                if j == 0 {
-                    println!("Player {}", i)
-                };
-                println!(
-                    "| {} - {}",
-                    card,
-                    card.allowed_bonus
-                    .to_uppercase()
-                    .custom_color(CustomColor::new(106, 229, 218))
-                );
-                for bonus in card.bonus.iter() {
-                    println!("| ↳ {}", bonus)
-                }
-            }
+        match self.top_discard() {
+            Some(card) => println!("{}", trf("heading.discard", &[card])),
+            None => println!("{}", tr("heading.discard_empty")),
         }
 
         println!();
 
+        // All player's played piles
+        self.show_piles();
+        println!();
+
+        if tracker::card_tracker() {
+            self.show_unseen();
+            println!();
+        }
+
         // let mut row_spec = String::new();
         // for _ in 0..self.players.len() {
         //     row_spec.push_str("| {:<}   ");
@@ -307,4 +393,40 @@ impl Board {
 
         // println!("{}", table);
     }
+
+    /// Every player's pile of countries, with the bonuses played on them
+    pub(crate) fn show_piles(&self) {
+        let card_style = CardStyle::current();
+        if card_style != CardStyle::Compact {
+            self.show_boxed_piles(card_style);
+            return;
+        }
+
+        for player in self.players.iter() {
+            for (j, card) in player.pile.iter().enumerate() {
+                if j == 0 {
+                    println!("{}", player.name)
+                };
+                println!(
+                    "| {} - {}",
+                    card,
+                    Theme::current()
+                        .bonus
+                        .paint(&card.allowed_bonus.to_uppercase())
+                );
+                for bonus in card.bonus.iter() {
+                    println!("| ↳ {}", bonus)
+                }
+            }
+        }
+    }
+
+    /// Every player's banked score
+    pub fn show_scores(&self) {
+        println!();
+        println!("{}", tr("heading.final_scores"));
+        for player in &self.players {
+            println!("| {}: {}", player.name, player.score);
+        }
+    }
 }
@@ -1,8 +1,663 @@
-use backpacker::Board;
+use std::{
+    error::Error,
+    io::{BufRead, BufReader},
+    net::TcpStream,
+    path::{Path, PathBuf},
+    process,
+    time::Duration,
+};
+
+use backpacker::{
+    Board, CardStyle, Deck, DeckProblem, DefaultAction, GameServer, Locale, Match, OutputFormat,
+    Profiles, Replay, Rules, Spectators, Strategy, Theme, Tournament, TurnTimer,
+};
+use clap::{Args, Parser, Subcommand};
+use tabular::{Row, Table};
+
+/// The Backpacker card game. Plays a game at the terminal when no command is given.
+#[derive(Parser)]
+#[command(version, args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(flatten)]
+    display: DisplayArgs,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    play: PlayArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Play a game at the terminal, or a match of several games
+    Play(PlayArgs),
+    /// Play games between bots and show the scores
+    Simulate(SimulateArgs),
+    /// Watch a game saved with --record
+    Replay { path: PathBuf },
+    /// Show the cards in a deck, in deck file format, or a report on its shape
+    DeckInfo {
+        /// Deck file to show instead of the standard deck
+        #[arg(long, value_name = "PATH")]
+        deck: Option<PathBuf>,
+        /// Show the deck's shape instead: countries and scores by continent, bonuses, the
+        /// best trip score and the share of grey, advice and special cards. A JSON object
+        /// with --format jsonl
+        #[arg(long)]
+        report: bool,
+        /// Check the deck for mistakes instead, like unknown countries or bonuses no country
+        /// allows, listing every one found
+        #[arg(long, conflicts_with = "report")]
+        check: bool,
+        /// Number of players to check the deck has enough cards for
+        #[arg(short = 'n', long, default_value_t = 2, requires = "check")]
+        players: usize,
+    },
+    /// Rank bot strategies by playing every pair of them against each other
+    Tournament(TournamentArgs),
+    /// Host games for clients over HTTP, with JSON bodies
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        address: String,
+    },
+    /// Watch a game started with --spectate, printing what happens as JSON lines
+    Spectate {
+        /// Address the game is being played at, like `127.0.0.1:7878`
+        address: String,
+    },
+}
+
+#[derive(Args)]
+#[command(next_help_heading = "Display")]
+struct DisplayArgs {
+    /// Language to show game text in, like `en` or `es`. Defaults to $LANG
+    #[arg(long, global = true)]
+    lang: Option<String>,
+    /// Turn colour off, showing continent tags instead
+    #[arg(long, global = true)]
+    no_colour: bool,
+    #[arg(long, global = true, value_name = "NAME|PATH", help = theme_help())]
+    theme: Option<String>,
+    /// Use the colour-blind theme, with continent tags
+    #[arg(long, global = true)]
+    colour_blind: bool,
+    /// How cards are drawn
+    #[arg(long, global = true, value_parser = ["compact", "boxed", "ascii"])]
+    cards: Option<String>,
+    /// Show continent tags on countries
+    #[arg(long, global = true)]
+    tags: bool,
+    /// List the cards you haven't seen yet, by type and continent, at the start of your turn
+    #[arg(long, global = true)]
+    track_cards: bool,
+    /// Text for people, or one JSON object per line for other programs
+    #[arg(long, global = true, value_parser = ["human", "jsonl"])]
+    format: Option<String>,
+    /// Milliseconds to pause after each step of a game shown at the terminal
+    #[arg(long, global = true, value_name = "MS")]
+    pace: Option<u64>,
+}
+
+#[derive(Args)]
+struct RulesArgs {
+    /// JSON file of rule variants, like `{"draw_from_discard": true}`
+    #[arg(long, value_name = "PATH")]
+    rules: Option<PathBuf>,
+    /// Let players take the top discard instead of drawing from the deck, unless it was put
+    /// there this turn
+    #[arg(long)]
+    draw_from_discard: bool,
+}
+
+#[derive(Args)]
+struct GameArgs {
+    /// Number of players
+    #[arg(short = 'n', long, default_value_t = 2)]
+    players: usize,
+    /// Put a bot in a seat, like `2=greedy`. Can be given once per seat
+    #[arg(long = "bot", value_name = "SEAT=STRATEGY")]
+    bots: Vec<String>,
+    #[command(flatten)]
+    rules: RulesArgs,
+    /// Deal from this deck file instead of the standard deck
+    #[arg(long, value_name = "PATH")]
+    deck: Option<PathBuf>,
+    /// Shuffle with this seed, for a repeatable deal
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Save the game to this file so it can be watched again with `replay`
+    #[arg(long, value_name = "PATH")]
+    record: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct PlayArgs {
+    #[command(flatten)]
+    game: GameArgs,
+    /// Play a match of this many games, with scores carried across games
+    #[arg(long)]
+    games: Option<usize>,
+    /// Where to save match progress, after each game and when a game is left unfinished
+    #[arg(long, value_name = "PATH", default_value = "match.json")]
+    save: PathBuf,
+    /// Continue a match saved with --games, including any game left unfinished
+    #[arg(long, value_name = "PATH")]
+    resume: Option<PathBuf>,
+    /// Carry on a game saved with the `save` command
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["games", "resume", "deck", "seed"]
+    )]
+    load: Option<PathBuf>,
+    /// Give each player this many seconds a turn. Only turns played at the terminal are timed,
+    /// so this can't be used with --format jsonl
+    #[arg(long, value_name = "SECONDS")]
+    turn_time: Option<u64>,
+    /// What happens when a turn runs out of time: `discard` the lowest card, or a strategy
+    /// plays the turn
+    #[arg(
+        long,
+        value_name = "ACTION",
+        default_value = "discard",
+        requires = "turn_time"
+    )]
+    on_timeout: String,
+    /// Let people watch over TCP at this address, like `127.0.0.1:7878`
+    #[arg(long, value_name = "ADDRESS")]
+    spectate: Option<String>,
+    /// Show spectators everyone's hand this many turns late, and the rest after the game
+    #[arg(long, value_name = "TURNS", requires = "spectate")]
+    reveal_delay: Option<usize>,
+    /// Where player profiles are kept
+    #[arg(long, value_name = "PATH", default_value = "profiles.json")]
+    profiles: PathBuf,
+}
+
+#[derive(Args)]
+struct SimulateArgs {
+    #[command(flatten)]
+    game: GameArgs,
+    /// Number of games to play, the seed going up by one each game
+    #[arg(long, default_value_t = 1)]
+    games: usize,
+    /// Strategy for every seat not given one with --bot
+    #[arg(long, default_value = "greedy")]
+    strategy: String,
+}
+
+#[derive(Args)]
+struct TournamentArgs {
+    /// Games to play between each pair of strategies
+    #[arg(long, default_value_t = 100)]
+    games: usize,
+    /// Seed for the deals and any random strategies
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// Strategies to enter, separated by commas. Defaults to all of them
+    #[arg(long, value_delimiter = ',')]
+    strategies: Vec<String>,
+    #[command(flatten)]
+    rules: RulesArgs,
+    /// Also write the results to this file
+    #[arg(long, value_name = "PATH")]
+    output: Option<PathBuf>,
+}
+
+impl DisplayArgs {
+    fn apply(&self) -> Result<(), Box<dyn Error>> {
+        let language = self.lang.clone().or_else(|| std::env::var("LANG").ok());
+        if let Some(locale) = language.as_deref().and_then(Locale::parse) {
+            locale.set();
+        }
+
+        backpacker::detect_colour();
+        if self.no_colour {
+            backpacker::set_colour(false);
+            backpacker::set_tags(true);
+        }
+        if let Some(theme) = &self.theme {
+            theme_named(theme)?.set();
+        }
+        if self.colour_blind {
+            Theme::builtin("colour-blind").unwrap().set();
+            backpacker::set_tags(true);
+        }
+        if let Some(style) = self.cards.as_deref().and_then(CardStyle::parse) {
+            style.set();
+        }
+        if self.tags {
+            backpacker::set_tags(true);
+        }
+        if self.track_cards {
+            backpacker::set_card_tracker(true);
+        }
+        if let Some(format) = self.format.as_deref().and_then(OutputFormat::parse) {
+            format.set();
+        }
+        if let Some(pace) = self.pace {
+            backpacker::set_pace(pace);
+        }
+
+        Ok(())
+    }
+}
+
+fn theme_help() -> String {
+    format!(
+        "A built-in theme ({}) or a theme file",
+        Theme::builtin_names().join(", ")
+    )
+}
+
+/// A built-in theme, or else the theme file at `name`
+fn theme_named(name: &str) -> Result<Theme, String> {
+    if let Some(theme) = Theme::builtin(name) {
+        return Ok(theme);
+    }
+    let path = Path::new(name);
+    if !path.exists() {
+        return Err(format!(
+            "`{}` isn't a built-in theme ({}) or a theme file",
+            name,
+            Theme::builtin_names().join(", ")
+        ));
+    }
+    Theme::load(path)
+}
+
+impl RulesArgs {
+    fn rules(&self) -> Result<Rules, String> {
+        let mut rules = match &self.rules {
+            Some(path) => Rules::load(path)?,
+            None => Rules::default(),
+        };
+        if self.draw_from_discard {
+            rules.draw_from_discard = true;
+        }
+        Ok(rules)
+    }
+}
+
+impl GameArgs {
+    /// The deck to play with, refusing one the game can't be played with and warning about
+    /// anything else `deck-info --check` would find
+    fn deck(&self) -> Result<Deck, String> {
+        let deck = load_deck(self.deck.as_deref())?;
+        if let Err(problems) = deck.validate(self.players) {
+            if problems.problems.iter().any(DeckProblem::is_fatal) {
+                return Err(problems.to_string());
+            }
+            eprintln!("Warning: {}", problems);
+        }
+        Ok(deck)
+    }
+
+    fn new_board(&self, deck: Deck, seed: u64) -> Result<Board, Box<dyn Error>> {
+        let rules = self.rules.rules()?;
+        Ok(Board::new_game_with_deck(self.players, rules, deck, seed)?)
+    }
+
+    /// The strategy playing each of `players` seats, `default` for seats not given one with --bot
+    fn strategies(
+        &self,
+        players: usize,
+        default: Option<&str>,
+    ) -> Result<Vec<Option<String>>, String> {
+        let mut strategies = vec![default.map(str::to_string); players];
+        let mut given = vec![false; players];
+
+        for bot in &self.bots {
+            let (seat, name) = bot
+                .split_once('=')
+                .ok_or_else(|| format!("--bot takes SEAT=STRATEGY, not `{}`", bot))?;
+            let seat = seat
+                .parse::<usize>()
+                .ok()
+                .filter(|seat| (1..=players).contains(seat))
+                .ok_or_else(|| format!("--bot seat `{}` isn't between 1 and {}", seat, players))?;
+            if !backpacker::strategy_names().contains(&name) {
+                return Err(format!(
+                    "unknown strategy `{}`, pick from {}",
+                    name,
+                    backpacker::strategy_names().join(", ")
+                ));
+            }
+            if given[seat - 1] {
+                return Err(format!("seat {} is given more than one bot", seat));
+            }
+
+            given[seat - 1] = true;
+            strategies[seat - 1] = Some(name.to_string());
+        }
+
+        Ok(strategies)
+    }
+}
+
+/// Build the bot for each seat that has a strategy, seeded differently per seat
+fn bots(
+    strategies: &[Option<String>],
+    seed: u64,
+) -> Result<Vec<Option<Box<dyn Strategy>>>, String> {
+    strategies
+        .iter()
+        .enumerate()
+        .map(|(seat, name)| match name {
+            Some(name) => backpacker::strategy(name, seed.wrapping_add(seat as u64))
+                .map(Some)
+                .ok_or_else(|| format!("unknown strategy `{}`", name)),
+            None => Ok(None),
+        })
+        .collect()
+}
+
+/// Names can only be picked interactively in human mode, so seats keep their default names
+/// otherwise. `None` if input ran out before everyone was named.
+fn choose_names(profiles: &Profiles, seats: &[usize]) -> Option<Vec<Option<String>>> {
+    match OutputFormat::current() {
+        OutputFormat::Human => profiles.choose_names(seats),
+        OutputFormat::Jsonl => Some(vec![None; seats.len()]),
+    }
+}
+
+impl PlayArgs {
+    fn turn_timer(&self, seed: u64) -> Result<Option<TurnTimer>, String> {
+        let Some(seconds) = self.turn_time else {
+            return Ok(None);
+        };
+        if OutputFormat::current() == OutputFormat::Jsonl {
+            return Err("--turn-time can't be used with --format jsonl".into());
+        }
+        let default = DefaultAction::parse(&self.on_timeout, seed).ok_or_else(|| {
+            format!(
+                "--on-timeout takes `discard` or a strategy from {}, not `{}`",
+                backpacker::strategy_names().join(", "),
+                self.on_timeout
+            )
+        })?;
+        Ok(Some(TurnTimer::new(Duration::from_secs(seconds), default)))
+    }
+
+    fn spectators(&self) -> Result<Option<Spectators>, String> {
+        let Some(address) = &self.spectate else {
+            return Ok(None);
+        };
+        let spectators = Spectators::listen(address.as_str())
+            .map_err(|e| format!("Couldn't listen on {}: {}", address, e))?
+            .with_reveal_delay(self.reveal_delay);
+        // Kept off stdout so it can't get mixed up with JSON lines
+        eprintln!("Spectators can watch at {}", spectators.address());
+        Ok(Some(spectators))
+    }
+}
+
+fn play(args: PlayArgs) -> Result<(), Box<dyn Error>> {
+    // Checks there are few enough players for the deck before anything else is set up
+    let deck = args.game.deck()?;
+    let mut profiles = Profiles::load(&args.profiles)
+        .map_err(|e| format!("Couldn't load {}: {}", args.profiles.display(), e))?;
+    let seed = args.game.seed.unwrap_or_else(rand::random);
+    let timer = args.turn_timer(seed)?;
+    let spectators = args.spectators()?;
+
+    if let Some(path) = &args.resume {
+        let mut game_match = Match::load(path)
+            .map_err(|e| format!("Couldn't load {}: {}", path.display(), e))?
+            .with_turn_timer(timer)
+            .with_spectators(spectators);
+        game_match.show_standings();
+        game_match.play(Some(path), Some(&mut profiles))?;
+        return Ok(());
+    }
+
+    let game = &args.game;
+
+    if let Some(games) = args.games {
+        if !game.bots.is_empty()
+            || game.deck.is_some()
+            || game.seed.is_some()
+            || game.record.is_some()
+        {
+            return Err("--bot, --deck, --seed and --record can't be used with --games".into());
+        }
+
+        let rules = game.rules.rules()?;
+
+        let seats: Vec<usize> = (0..game.players).collect();
+        let Some(names) = choose_names(&profiles, &seats) else {
+            return Ok(());
+        };
+        let mut game_match = Match::new(game.players, games, rules)
+            .with_names(names)
+            .with_turn_timer(timer)
+            .with_spectators(spectators);
+        game_match.play(Some(&args.save), Some(&mut profiles))?;
+        return Ok(());
+    }
+
+    // Only people who gave a name have the game added to their profile
+    let (mut board, mut bots, named) = match &args.load {
+        // The saved game already has its players named
+        Some(path) => {
+            let board = Replay::load(path)
+                .map_err(|e| format!("Couldn't load {}: {}", path.display(), e))?
+                .resume()?;
+            let bots = bots(&game.strategies(board.names().len(), None)?, seed)?;
+            let named = board
+                .named_seats()
+                .into_iter()
+                .filter(|&seat| bots[seat].is_none())
+                .collect();
+            (board, bots, named)
+        }
+        None => {
+            let mut board = game.new_board(deck, seed)?;
+            let bots = bots(&game.strategies(game.players, None)?, seed)?;
+
+            let seats: Vec<usize> = (0..game.players)
+                .filter(|&seat| bots[seat].is_none())
+                .collect();
+            let Some(chosen) = choose_names(&profiles, &seats) else {
+                return Ok(());
+            };
+            let mut names = board.names();
+            let mut named = Vec::new();
+            for (&seat, name) in seats.iter().zip(chosen) {
+                if let Some(name) = name {
+                    names[seat] = name;
+                    named.push(seat);
+                }
+            }
+            board.set_names(&names);
+            (board, bots, named)
+        }
+    };
+    board.set_turn_timer(timer);
+    board.set_spectators(spectators);
+    board.play_game(&mut bots);
+
+    if let Some(path) = &game.record {
+        board.replay().save(path)?;
+    }
+    // A game left unfinished doesn't count towards anyone's record
+    if !board.is_abandoned() {
+        profiles.record_game(&board, &named);
+    }
+    profiles.save()?;
+    Ok(())
+}
+
+fn simulate(args: SimulateArgs) -> Result<(), Box<dyn Error>> {
+    let game = &args.game;
+    if game.record.is_some() && args.games != 1 {
+        return Err("--record can only be used when simulating one game".into());
+    }
+
+    // Checks there are few enough players for the deck before anything is made for each seat
+    let deck = game.deck()?;
+    let strategies = game.strategies(game.players, Some(&args.strategy))?;
+    let first_seed = game.seed.unwrap_or_else(rand::random);
+    let jsonl = OutputFormat::current() == OutputFormat::Jsonl;
+
+    let mut table = Table::new(&format!("{{:<}}{}", "  {:>}".repeat(game.players)));
+    let mut heading = Row::new().with_cell("seed");
+    for (seat, strategy) in strategies.iter().enumerate() {
+        heading.add_cell(format!(
+            "{} ({})",
+            seat + 1,
+            strategy.as_deref().unwrap_or_default()
+        ));
+    }
+    table.add_row(heading);
+    let mut wins = vec![0; game.players];
+
+    for i in 0..args.games {
+        let seed = first_seed.wrapping_add(i as u64);
+        let mut board = game.new_board(deck.clone(), seed)?;
+        // Simulations audit the cards even in release builds
+        board.set_debug_audit(true);
+        let mut bots: Vec<Box<dyn Strategy>> =
+            bots(&strategies, seed)?.into_iter().flatten().collect();
+        board.bot_game(&mut bots);
+
+        if jsonl {
+            let result = serde_json::json!({
+                "type": "game_over",
+                "seed": seed,
+                "scores": board.scores(),
+                "winners": board.winners(),
+            });
+            println!("{}", result);
+        }
+
+        let mut row = Row::new().with_cell(seed);
+        for score in board.scores() {
+            row.add_cell(score);
+        }
+        table.add_row(row);
+        for seat in board.winners() {
+            wins[seat] += 1;
+        }
+
+        if let Some(path) = &game.record {
+            board.replay().save(path)?;
+        }
+    }
+
+    if !jsonl {
+        let mut row = Row::new().with_cell("wins");
+        for count in wins {
+            row.add_cell(count);
+        }
+        table.add_row(row);
+        print!("{}", table);
+    }
+
+    Ok(())
+}
+
+fn replay(path: &Path) -> Result<(), Box<dyn Error>> {
+    if OutputFormat::current() == OutputFormat::Jsonl {
+        return Err("replays can only be watched with --format human".into());
+    }
+
+    let replay =
+        Replay::load(path).map_err(|e| format!("Couldn't load {}: {}", path.display(), e))?;
+    let board = replay.watch()?;
+    board.show_scores();
+    Ok(())
+}
+
+fn serve(address: &str) -> Result<(), Box<dyn Error>> {
+    let server =
+        GameServer::bind(address).map_err(|e| format!("Couldn't listen on {}: {}", address, e))?;
+    eprintln!("Serving games at http://{}", server.address());
+    server.run();
+    Ok(())
+}
+
+fn spectate(address: &str) -> Result<(), Box<dyn Error>> {
+    let stream = TcpStream::connect(address)
+        .map_err(|e| format!("Couldn't connect to {}: {}", address, e))?;
+    for line in BufReader::new(stream).lines() {
+        println!("{}", line?);
+    }
+    Ok(())
+}
+
+fn load_deck(path: Option<&Path>) -> Result<Deck, String> {
+    match path {
+        Some(path) => Deck::load(path),
+        None => Ok(Deck::standard()),
+    }
+}
+
+fn deck_info(path: Option<&Path>, report: bool) -> Result<(), Box<dyn Error>> {
+    let deck = load_deck(path)?;
+
+    if report {
+        let report = deck.report();
+        match OutputFormat::current() {
+            OutputFormat::Human => print!("{}", report),
+            OutputFormat::Jsonl => println!("{}", serde_json::to_string(&report)?),
+        }
+        return Ok(());
+    }
+
+    println!("# {} cards", deck.len());
+    print!("{}", deck);
+    Ok(())
+}
+
+fn check_deck(path: Option<&Path>, players: usize) -> Result<(), Box<dyn Error>> {
+    load_deck(path)?.validate(players)?;
+    println!("No problems found for {} players", players);
+    Ok(())
+}
+
+fn tournament(args: TournamentArgs) -> Result<(), Box<dyn Error>> {
+    let mut tournament = Tournament::new(args.games, args.seed, args.rules.rules()?);
+    if !args.strategies.is_empty() {
+        tournament = tournament.with_strategies(args.strategies);
+    }
+
+    let results = tournament.run()?;
+    print!("{}", results);
+    if let Some(path) = &args.output {
+        results.write(path)?;
+    }
+    Ok(())
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    cli.display.apply()?;
+
+    match cli.command {
+        None => play(cli.play),
+        Some(Command::Play(args)) => play(args),
+        Some(Command::Simulate(args)) => simulate(args),
+        Some(Command::Replay { path }) => replay(&path),
+        Some(Command::DeckInfo {
+            deck,
+            check,
+            players,
+            ..
+        }) if check => check_deck(deck.as_deref(), players),
+        Some(Command::DeckInfo { deck, report, .. }) => deck_info(deck.as_deref(), report),
+        Some(Command::Tournament(args)) => tournament(args),
+        Some(Command::Serve { address }) => serve(&address),
+        Some(Command::Spectate { address }) => spectate(&address),
+    }
+}
 
 fn main() {
-    let mut board = Board::new_game(2);
-    board.manual_game();
+    if let Err(e) = run(Cli::parse()) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
 }
 
 // let to_have = [
@@ -18,4 +673,4 @@ fn main() {
 //     if to_have.iter().all(|func| func(cards)) {
 //         break;
 //     }
-// }
\ No newline at end of file
+// }
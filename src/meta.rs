@@ -0,0 +1,188 @@
+use std::path::Path;
+
+use crate::{
+    Board,
+    locale::{tr, trf},
+    read_line,
+};
+
+const DEFAULT_SAVE: &str = "game.json";
+
+/// A command that can be typed at any prompt during a game without answering it
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Meta {
+    Help,
+    Scores,
+    Piles,
+    Discards,
+    Status,
+    Risk,
+    Save(String),
+    Quit,
+}
+
+// Each meta command's English word, always understood, and its catalogue key for the current locale
+const WORDS: &[(&str, &str)] = &[
+    ("help", "meta.help"),
+    ("scores", "meta.scores"),
+    ("piles", "meta.piles"),
+    ("discards", "meta.discards"),
+    ("status", "meta.status"),
+    ("risk", "meta.risk"),
+    ("save", "meta.save"),
+    ("quit", "meta.quit"),
+];
+
+fn parse_meta(input: &str) -> Option<Meta> {
+    let input = input.trim();
+    let (word, rest) = input.split_once(' ').unwrap_or((input, ""));
+    let word = word.to_lowercase();
+    let (english, _) = WORDS
+        .iter()
+        .find(|(english, key)| word == *english || word == tr(key))?;
+
+    let meta = match *english {
+        "save" if rest.trim().is_empty() => Meta::Save(DEFAULT_SAVE.to_string()),
+        "save" => Meta::Save(rest.trim().to_string()),
+        // Anything after the other words means it's an answer to the prompt instead
+        _ if !rest.is_empty() => return None,
+        "help" => Meta::Help,
+        "scores" => Meta::Scores,
+        "piles" => Meta::Piles,
+        "discards" => Meta::Discards,
+        "status" => Meta::Status,
+        "risk" => Meta::Risk,
+        _ => Meta::Quit,
+    };
+    Some(meta)
+}
+
+impl Board {
+    /// Ask the current player with `message` until `parse` accepts the answer, showing why it
+    /// didn't. Meta commands like `scores` are answered in between without using up the prompt.
+    ///
+    /// Returns `None` if the player quits or stdin is closed, leaving the game abandoned, or
+    /// if the turn timer runs out first.
+    pub(crate) fn ask<T, F>(&mut self, message: &str, parse: F) -> Option<T>
+    where
+        F: Fn(&Board, &str) -> Result<T, String>,
+    {
+        loop {
+            match self.time_left() {
+                Some(time_left) => println!("{} ({})", message, time_left),
+                None => println!("{}", message),
+            }
+            let line = match &self.timer {
+                Some(timer) => timer.read_line()?,
+                None => read_line(),
+            };
+            let line = match line {
+                Ok(line) if line.is_empty() => {
+                    self.abandon();
+                    return None;
+                }
+                Ok(line) => line,
+                Err(_) => {
+                    println!("{}\n", tr("prompt.read_error"));
+                    continue;
+                }
+            };
+
+            match parse_meta(&line) {
+                Some(Meta::Quit) => {
+                    println!("{}", tr("meta.quitting"));
+                    self.abandon();
+                    return None;
+                }
+                Some(meta) => {
+                    self.run_meta(meta);
+                    continue;
+                }
+                None => {}
+            }
+
+            match parse(self, &line) {
+                Ok(answer) => return Some(answer),
+                Err(e) => println!("{}\n", e),
+            }
+        }
+    }
+
+    fn run_meta(&self, meta: Meta) {
+        println!();
+        match meta {
+            Meta::Help => println!("{}", tr("meta.help_text")),
+            Meta::Scores => {
+                for player in &self.players {
+                    let score = trf(
+                        "meta.score_line",
+                        &[&player.name, &player.score, &player.trip_score()],
+                    );
+                    println!("| {}", score);
+                }
+            }
+            Meta::Piles => self.show_piles(),
+            Meta::Discards => {
+                if self.past.is_empty() {
+                    println!("{}", tr("heading.discard_empty"));
+                }
+                // Top of the pile first
+                for card in self.past.iter().rev() {
+                    println!("| {}", card);
+                }
+            }
+            Meta::Status => {
+                let player = &self.players[self.turn];
+                println!("{}", trf("heading.turn", &[&player.name]));
+                println!("{}", trf("meta.deck_left", &[&self.future.len()]));
+                println!("{}", trf("meta.discard_size", &[&self.past.len()]));
+                if player.status.hazards.is_empty() {
+                    println!("{}", tr("meta.no_hazards"));
+                } else {
+                    println!("{}", tr("heading.hazards"));
+                    for hazard in &player.status.hazards {
+                        println!("| {}", hazard);
+                    }
+                }
+            }
+            Meta::Risk => self.show_risk(),
+            Meta::Save(path) => match self.replay().save(Path::new(&path)) {
+                Ok(()) => println!("{}", trf("meta.saved", &[&path])),
+                Err(e) => println!("{}", trf("meta.save_failed", &[&path, &e])),
+            },
+            Meta::Quit => {}
+        }
+        println!();
+    }
+
+    /// The odds of going home now against playing on, for the current player
+    fn show_risk(&self) {
+        let percent = |chance: f64| format!("{:.0}%", chance * 100.0);
+        let risk = self
+            .go_home_risk(self.turn)
+            .expect("The current seat is always at the table");
+
+        match risk.home_now {
+            Some(points) => println!("{}", trf("risk.home_now", &[&points])),
+            None => println!("{}", tr("risk.grey_held")),
+        }
+        let play_on = format!("{:.1}", risk.play_on);
+        println!("{}", trf("risk.play_on", &[&play_on]));
+        println!("{}", trf("risk.turns_left", &[&risk.turns_left]));
+        println!("{}", trf("risk.draws", &[&risk.draws, &risk.unseen]));
+        let missed_flight = percent(risk.missed_flight);
+        println!("| {}", trf("risk.missed_flight", &[&missed_flight]));
+        let useful_bonus = percent(risk.useful_bonus);
+        println!("| {}", trf("risk.useful_bonus", &[&useful_bonus]));
+        println!("{}", tr("risk.most_likely"));
+        for (card, chance) in risk.next_draw.iter().take(5) {
+            println!("| {} {}", percent(*chance), card);
+        }
+
+        if risk.should_go_home() {
+            println!("{}", tr("risk.go_home"));
+        } else {
+            println!("{}", tr("risk.keep_going"));
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! Stands in for `style` in builds without a terminal, where cards are drawn as plain text
+
+use crate::Continent;
+
+/// Draws text as it is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Paint;
+
+impl Paint {
+    pub fn paint(&self, text: &str) -> String {
+        text.to_string()
+    }
+}
+
+/// The same paint for every kind of card
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Theme {
+    pub(crate) bonus: Paint,
+    pub(crate) money: Paint,
+    pub(crate) good: (Paint, Paint),
+    pub(crate) bad: (Paint, Paint),
+    pub(crate) grey: Paint,
+    pub(crate) special: Paint,
+}
+
+impl Theme {
+    pub fn current() -> Self {
+        Self {
+            bonus: Paint,
+            money: Paint,
+            good: (Paint, Paint),
+            bad: (Paint, Paint),
+            grey: Paint,
+            special: Paint,
+        }
+    }
+
+    pub(crate) fn continent(&self, _continent: Continent) -> Paint {
+        Paint
+    }
+}
+
+/// Without colour, continent tags and advice markers are always shown
+pub(crate) fn tags() -> bool {
+    true
+}
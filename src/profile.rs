@@ -0,0 +1,189 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Board, Continent, Event,
+    locale::{is_default_name, tr, trf},
+    read_line,
+};
+
+/// A named player and their lifetime stats
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub games: u32,
+    pub wins: u32,
+    // Times gone home with points, and the points banked doing it
+    trips: u32,
+    trip_points: u32,
+    // Countries played on each continent, over every game
+    continents: BTreeMap<Continent, u32>,
+}
+
+impl Profile {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn average_trip_score(&self) -> f64 {
+        if self.trips == 0 {
+            0.0
+        } else {
+            self.trip_points as f64 / self.trips as f64
+        }
+    }
+
+    /// The continent this player has visited the most
+    pub(crate) fn favourite_continent(&self) -> Option<Continent> {
+        self.continents
+            .iter()
+            .max_by_key(|(_, visits)| **visits)
+            .map(|(continent, _)| *continent)
+    }
+}
+
+/// Every known profile, kept in a local file
+#[derive(Debug)]
+pub struct Profiles {
+    path: PathBuf,
+    profiles: Vec<Profile>,
+}
+
+impl Profiles {
+    /// Load the profiles stored at `path`, or start with none if the file doesn't exist yet
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let profiles = match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(io::Error::other)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            profiles,
+        })
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let contents = serde_json::to_string_pretty(&self.profiles).map_err(io::Error::other)?;
+        fs::write(&self.path, contents)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    fn get_or_create(&mut self, name: &str) -> &mut Profile {
+        let index = match self
+            .profiles
+            .iter()
+            .position(|profile| profile.name == name)
+        {
+            Some(index) => index,
+            None => {
+                self.profiles.push(Profile::new(name));
+                self.profiles.len() - 1
+            }
+        };
+        &mut self.profiles[index]
+    }
+
+    /// Add a finished game to the stats of the players in `seats`. Only seats a person was
+    /// named for belong here: bots and default "Player N" names would fill the profiles with
+    /// strangers, one set for each language.
+    pub fn record_game(&mut self, board: &Board, seats: &[usize]) {
+        let winners = board.winners();
+        let names = board.names();
+
+        for &seat in seats {
+            let profile = self.get_or_create(&names[seat]);
+            profile.games += 1;
+            if winners.contains(&seat) {
+                profile.wins += 1;
+            }
+
+            for event in board.events() {
+                match event {
+                    Event::WentHome { seat: s, points } if *s == seat && *points > 0 => {
+                        profile.trips += 1;
+                        profile.trip_points += points;
+                    }
+                    Event::CountryPlayed { seat: s, country } if *s == seat => {
+                        *profile.continents.entry(country.continent()).or_default() += 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Ask who is sitting in each of `seats` (0-based), either picking an existing profile by
+    /// number or typing a new name. Leaving it blank keeps the default "Player N", given as
+    /// `None`.
+    ///
+    /// Returns `None` if input runs out or can't be read before every seat has a name.
+    pub fn choose_names(&self, seats: &[usize]) -> Option<Vec<Option<String>>> {
+        if !self.profiles.is_empty() {
+            println!("{}", tr("prompt.known_players"));
+            for (i, profile) in self.profiles.iter().enumerate() {
+                println!("| {} {}", i + 1, profile);
+            }
+            println!();
+        }
+
+        let shown = |name: &Option<String>, seat: usize| match name {
+            Some(name) => name.clone(),
+            None => trf("player.default_name", &[&(seat + 1)]),
+        };
+
+        let mut names: Vec<Option<String>> = Vec::new();
+        while names.len() < seats.len() {
+            let seat = seats[names.len()];
+            println!("{}", trf("prompt.who_is", &[&(seat + 1)]));
+
+            // An empty line is a newline, so nothing at all means stdin is closed
+            let input = match read_line() {
+                Ok(input) if !input.is_empty() => input.trim().to_string(),
+                _ => return None,
+            };
+
+            let name = match input.parse::<usize>() {
+                Ok(i) if (1..=self.profiles.len()).contains(&i) => {
+                    Some(self.profiles[i - 1].name.clone())
+                }
+                _ if input.is_empty() => None,
+                _ => Some(input),
+            };
+
+            let name_shown = shown(&name, seat);
+            if names
+                .iter()
+                .zip(seats)
+                .any(|(other, &other_seat)| shown(other, other_seat) == name_shown)
+            {
+                println!("{}", trf("prompt.already_playing", &[&name_shown]));
+            } else {
+                names.push(name);
+            }
+        }
+
+        Some(names)
+    }
+}
+
+impl Board {
+    /// Seats given a name of their own, rather than "Player N" in any language
+    pub fn named_seats(&self) -> Vec<usize> {
+        (0..self.players.len())
+            .filter(|&seat| !is_default_name(&self.players[seat].name, seat + 1))
+            .collect()
+    }
+}
@@ -0,0 +1,341 @@
+use std::sync::{Arc, Mutex};
+
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyList};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{Board, Env, Move, Phase, Rules, Session, Strategy, TurnCommand, strategy};
+
+// Views, moves and events cross over as the same JSON the HTTP server uses, turned into
+// Python dicts and lists
+fn to_python(py: Python<'_>, value: impl Serialize) -> PyResult<PyObject> {
+    let json = serde_json::to_string(&value).expect("Views always serialize");
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+fn from_python<T: DeserializeOwned>(value: &Bound<'_, PyAny>) -> PyResult<T> {
+    let json: String = value
+        .py()
+        .import("json")?
+        .call_method1("dumps", (value,))?
+        .extract()?;
+    serde_json::from_str(&json).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+fn rules(rules: Option<&Bound<'_, PyAny>>) -> PyResult<Rules> {
+    rules.map_or_else(|| Ok(Rules::default()), from_python)
+}
+
+/// A game played one move at a time, with bots taking their own turns in between.
+///
+/// Seats are numbered from 0. Moves are dicts like `{"command": "play", "index": 0}`, and
+/// once a seat has moved it may be asked to draw with `{"command": "draw", "from": "deck"}`.
+#[pyclass(name = "Game", module = "backpacker", unsendable)]
+struct PyGame {
+    session: Session,
+}
+
+#[pymethods]
+impl PyGame {
+    /// Deal a game. `rules` is a dict of rule switches, and `bots` names a strategy, or
+    /// `None`, for each seat.
+    #[new]
+    #[pyo3(signature = (players = 2, seed = None, rules = None, bots = None))]
+    fn new(
+        players: usize,
+        seed: Option<u64>,
+        rules: Option<&Bound<'_, PyAny>>,
+        bots: Option<Vec<Option<String>>>,
+    ) -> PyResult<Self> {
+        let seed = seed.unwrap_or_else(rand::random);
+        let bots = bots.unwrap_or_default();
+        let session = Session::new(players, self::rules(rules)?, seed, &bots)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { session })
+    }
+
+    /// Whose turn it is
+    #[getter]
+    fn turn(&self) -> usize {
+        self.session.board().turn
+    }
+
+    /// What the current player has still to do: `move`, `draw` or `game_over`
+    #[getter]
+    fn phase(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, self.session.phase())
+    }
+
+    #[getter]
+    fn players(&self) -> usize {
+        self.session.players()
+    }
+
+    fn is_over(&self) -> bool {
+        self.session.board().is_over()
+    }
+
+    /// Everything `seat` can see, including its own hand but nobody else's
+    fn view(&self, py: Python<'_>, seat: usize) -> PyResult<PyObject> {
+        self.check_seat(seat)?;
+        to_python(py, self.session.view(seat))
+    }
+
+    /// The moves `seat` can make now, which is none unless it's their turn to move
+    fn legal_moves(&self, py: Python<'_>, seat: usize) -> PyResult<PyObject> {
+        self.check_seat(seat)?;
+        to_python(py, self.session.moves(seat).legal_moves())
+    }
+
+    /// Play a move, or a draw, for `seat`. Raises `ValueError` if it isn't allowed.
+    fn apply_move(&mut self, seat: usize, command: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.check_seat(seat)?;
+        let command: TurnCommand = from_python(command)?;
+        self.session
+            .play(seat, command)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// The events after the first `since`
+    #[pyo3(signature = (since = 0))]
+    fn events(&self, py: Python<'_>, since: usize) -> PyResult<PyObject> {
+        to_python(
+            py,
+            self.session
+                .board()
+                .events()
+                .get(since..)
+                .unwrap_or_default(),
+        )
+    }
+
+    fn scores(&self) -> Vec<u32> {
+        self.session.board().scores()
+    }
+
+    /// Seats with the top score, once the game is over
+    fn winners(&self) -> Vec<usize> {
+        self.session.board().winners()
+    }
+
+    /// An independent copy of the game, bots and all, to try moves out on
+    fn clone(&self) -> Self {
+        Self {
+            session: self.session.clone(),
+        }
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+}
+
+impl PyGame {
+    fn check_seat(&self, seat: usize) -> PyResult<()> {
+        if seat < self.session.players() {
+            Ok(())
+        } else {
+            Err(PyValueError::new_err(format!("no seat {}", seat)))
+        }
+    }
+}
+
+/// A game for training an agent in seat 0 against bots in every other seat. Observations
+/// are lists of numbers laid out as `Env` describes in the Rust docs, and actions are
+/// numbers below `action_count`.
+#[pyclass(name = "Env", module = "backpacker", unsendable)]
+struct PyEnv {
+    env: Env,
+}
+
+#[pymethods]
+impl PyEnv {
+    #[new]
+    #[pyo3(signature = (players = 2, opponent = "random", rules = None))]
+    fn new(players: usize, opponent: &str, rules: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        if strategy(opponent, 0).is_none() {
+            let message = format!("unknown strategy `{}`", opponent);
+            return Err(PyValueError::new_err(message));
+        }
+        let env = Env::new(players, self::rules(rules)?).with_opponent(opponent);
+        Ok(Self { env })
+    }
+
+    #[getter]
+    fn observation_size(&self) -> usize {
+        self.env.observation_size()
+    }
+
+    #[getter]
+    fn action_count(&self) -> usize {
+        Env::ACTIONS
+    }
+
+    /// Deal a new game and return the first observation
+    #[pyo3(signature = (seed = None))]
+    fn reset(&mut self, seed: Option<u64>) -> PyResult<Vec<f32>> {
+        let seed = seed.unwrap_or_else(rand::random);
+        self.env
+            .reset(seed)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Take `action` and return the observation, reward and whether the game is over.
+    /// Raises `ValueError` for an action the mask rules out.
+    fn step(&mut self, action: usize) -> PyResult<(Vec<f32>, f32, bool)> {
+        let step = self
+            .env
+            .step(action)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok((step.observation, step.reward, step.done))
+    }
+
+    /// Which actions can be taken now
+    fn action_mask(&self) -> Vec<bool> {
+        self.env.action_mask().to_vec()
+    }
+}
+
+/// A strategy written in Python: either a function taking a seat's view and the legal moves
+/// and returning one of them, or an object with a `choose_move` method like that and an
+/// optional `take_discard(view)` method
+struct PyStrategy {
+    strategy: PyObject,
+    // The first exception the strategy raised, or the first bad move it made. Once there is
+    // one, the rest of the game is played out with the first legal move.
+    error: Arc<Mutex<Option<PyErr>>>,
+}
+
+impl Clone for PyStrategy {
+    fn clone(&self) -> Self {
+        Python::with_gil(|py| Self {
+            strategy: self.strategy.clone_ref(py),
+            error: self.error.clone(),
+        })
+    }
+}
+
+impl PyStrategy {
+    fn try_choose_move(&self, py: Python<'_>, board: &Board, moves: &[Move]) -> PyResult<Move> {
+        let view = to_python(py, board.seat_view(board.turn, Phase::Move))?;
+        let legal = to_python(py, moves)?;
+        let strategy = self.strategy.bind(py);
+        let chosen = if strategy.hasattr("choose_move")? {
+            strategy.call_method1("choose_move", (view, legal))?
+        } else {
+            strategy.call1((view, legal))?
+        };
+
+        let chosen: Move = from_python(&chosen)?;
+        if moves.contains(&chosen) {
+            Ok(chosen)
+        } else {
+            let chosen = serde_json::to_string(&chosen).expect("Moves always serialize");
+            Err(PyValueError::new_err(format!(
+                "{} isn't a legal move",
+                chosen
+            )))
+        }
+    }
+
+    fn try_take_discard(&self, py: Python<'_>, board: &Board) -> PyResult<bool> {
+        let strategy = self.strategy.bind(py);
+        if !strategy.hasattr("take_discard")? {
+            return Ok(false);
+        }
+        let view = to_python(py, board.seat_view(board.turn, Phase::Draw))?;
+        strategy.call_method1("take_discard", (view,))?.extract()
+    }
+
+    // Keep the first error, so it can be raised once the game is over
+    fn record<T>(&self, result: PyResult<T>, fallback: T) -> T {
+        result.unwrap_or_else(|e| {
+            self.error.lock().unwrap().get_or_insert(e);
+            fallback
+        })
+    }
+
+    fn failed(&self) -> bool {
+        self.error.lock().unwrap().is_some()
+    }
+}
+
+impl Strategy for PyStrategy {
+    fn choose_move(&mut self, board: &Board, moves: &[Move]) -> Move {
+        if self.failed() {
+            return moves[0];
+        }
+        let result = Python::with_gil(|py| self.try_choose_move(py, board, moves));
+        self.record(result, moves[0])
+    }
+
+    fn take_discard(&mut self, board: &Board) -> bool {
+        if self.failed() {
+            return false;
+        }
+        let result = Python::with_gil(|py| self.try_take_discard(py, board));
+        self.record(result, false)
+    }
+}
+
+/// Play a whole game with a strategy in every seat, each either the name of a built-in
+/// strategy or a Python one, and return the final scores and winners along with every
+/// event. Raises whatever a Python strategy raised, or `ValueError` for an illegal move.
+#[pyfunction]
+#[pyo3(signature = (strategies, seed = None, rules = None))]
+fn play_game(
+    py: Python<'_>,
+    strategies: &Bound<'_, PyList>,
+    seed: Option<u64>,
+    rules: Option<&Bound<'_, PyAny>>,
+) -> PyResult<PyObject> {
+    let seed = seed.unwrap_or_else(rand::random);
+    let error = Arc::default();
+    let mut bots = strategies
+        .iter()
+        .enumerate()
+        .map(|(seat, entry)| match entry.extract::<String>() {
+            Ok(name) => strategy(&name, seed.wrapping_add(seat as u64))
+                .ok_or_else(|| PyValueError::new_err(format!("unknown strategy `{}`", name))),
+            Err(_) => Ok(Box::new(PyStrategy {
+                strategy: entry.unbind(),
+                error: Arc::clone(&error),
+            }) as Box<dyn Strategy>),
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let mut board = Board::new_seeded_game(bots.len(), self::rules(rules)?, seed)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    board.bot_game(&mut bots);
+
+    if let Some(e) = error.lock().unwrap().take() {
+        return Err(e);
+    }
+    to_python(
+        py,
+        serde_json::json!({
+            "scores": board.scores(),
+            "winners": board.winners(),
+            "events": board.events(),
+        }),
+    )
+}
+
+/// The Backpacker card game engine
+#[pymodule]
+fn backpacker(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyGame>()?;
+    module.add_class::<PyEnv>()?;
+    module.add_function(wrap_pyfunction!(play_game, module)?)?;
+    module.add_function(wrap_pyfunction!(strategy_names, module)?)?;
+    Ok(())
+}
+
+/// Names of the built-in strategies
+#[pyfunction]
+fn strategy_names() -> Vec<&'static str> {
+    crate::strategy_names()
+}
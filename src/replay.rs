@@ -0,0 +1,135 @@
+use std::{fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "terminal")]
+use crate::pause;
+use crate::{Board, Deck, Move, Rules};
+
+/// A choice made by the current player, in the order the game needs them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Move(Move),
+    Draw { from_discard: bool },
+}
+
+/// Everything needed to play a game again exactly as it happened
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Replay {
+    num_players: usize,
+    rules: Rules,
+    seed: u64,
+    first_player: usize,
+    names: Vec<String>,
+    // The deck in deck file format, one line per entry
+    deck: Vec<String>,
+    actions: Vec<Action>,
+}
+
+impl Replay {
+    /// Load a replay saved by `Replay::save`
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        serde_json::from_str(&contents).map_err(io::Error::other)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let contents = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, contents)
+    }
+
+    /// Play the recorded game back, showing every turn. Stops where the recording does if
+    /// the game wasn't finished.
+    #[cfg(feature = "terminal")]
+    pub fn watch(&self) -> Result<Board, String> {
+        self.play_back(true)
+    }
+
+    /// Rebuild a saved game, ready to carry on playing. A turn that was only partly played
+    /// when the game was saved starts again from the beginning.
+    pub fn resume(&self) -> Result<Board, String> {
+        let mut board = self.play_back(false)?;
+        board.shown_events = board.events.len();
+        Ok(board)
+    }
+
+    fn play_back(&self, show: bool) -> Result<Board, String> {
+        let deck: Deck = self.deck.join("\n").parse()?;
+        let mut board =
+            Board::new_game_with_deck(self.num_players, self.rules.clone(), deck, self.seed)
+                .map_err(|e| e.to_string())?;
+        board.set_names(&self.names);
+        board.set_first_player(self.first_player);
+
+        let mismatch = |action: usize| {
+            format!(
+                "the recording doesn't match the game at action {}",
+                action + 1
+            )
+        };
+
+        if show {
+            board.game_heading();
+        }
+
+        // Every turn that isn't skipped is a move followed by a draw. Only whole turns are
+        // played back, so the game can carry on cleanly from wherever the recording stops.
+        let mut turns = self.actions.chunks_exact(2);
+        while !board.is_over() && turns.len() > 0 {
+            if show {
+                board.turn_heading();
+            }
+            if !board.begin_turn() {
+                if show {
+                    board.show_replayed(true);
+                }
+                continue;
+            }
+
+            let first = self.actions.len() - 2 * turns.len();
+            let &[Action::Move(mv), Action::Draw { from_discard }] = turns.next().unwrap() else {
+                return Err(mismatch(first));
+            };
+
+            board.apply_move(mv).map_err(|_| mismatch(first))?;
+            if show {
+                board.show_replayed(true);
+            }
+            board.end_turn(from_discard);
+            if show {
+                board.show_replayed(false);
+            }
+        }
+
+        Ok(board)
+    }
+}
+
+impl Board {
+    // Print what was just played back, then give the viewer time to read it if `wait`
+    #[cfg(feature = "terminal")]
+    fn show_replayed(&mut self, wait: bool) {
+        self.show_events();
+        if wait {
+            pause();
+        }
+    }
+
+    // Replays are only watched at the terminal
+    #[cfg(not(feature = "terminal"))]
+    fn show_replayed(&mut self, _wait: bool) {}
+
+    /// A recording of the game so far
+    pub fn replay(&self) -> Replay {
+        Replay {
+            num_players: self.players.len(),
+            rules: self.rules.clone(),
+            seed: self.seed,
+            first_player: self.first_player,
+            names: self.names(),
+            deck: self.deck.to_string().lines().map(str::to_string).collect(),
+            actions: self.actions.clone(),
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use serde::Serialize;
+
+use crate::{Board, Card, Country, GreyType, HAND_SIZE, Player};
+
+/// What a seat can work out about going home now, from the cards it can see: every pile,
+/// the discard pile, the hazards in play and its own hand. Everything else in the deck the
+/// game was dealt from is equally likely to be drawn next.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GoHomeRisk {
+    /// Points going home now would bank, or `None` while a grey card is held
+    pub home_now: Option<u32>,
+    /// The points expected from making the best play in hand and going home as soon as
+    /// possible instead
+    pub play_on: f64,
+    /// How many more turns the seat can expect before the deck runs out, if every seat
+    /// uses one card a turn
+    pub turns_left: usize,
+    /// Cards the seat hasn't seen, which the next draws come from
+    pub unseen: usize,
+    /// How many cards playing on would draw at the end of this turn
+    pub draws: usize,
+    /// The chance of those draws bringing a Missed Flight, which has to be got rid of
+    /// before going home
+    pub missed_flight: f64,
+    /// The chance of those draws bringing a bonus for the top country after the best play
+    pub useful_bonus: f64,
+    /// The chance of each different card being the next one drawn, most likely first
+    pub next_draw: Vec<(Card, f64)>,
+}
+
+impl GoHomeRisk {
+    /// Whether going home now is worth at least as much as playing on
+    pub fn should_go_home(&self) -> bool {
+        self.home_now
+            .is_some_and(|now| now > 0 && f64::from(now) >= self.play_on)
+    }
+}
+
+/// The chance of drawing at least one of `wanted` cards in `draws` cards taken from `unseen`
+fn chance_of_any(wanted: usize, unseen: usize, draws: usize) -> f64 {
+    let mut none = 1.0;
+    for i in 0..draws.min(unseen) {
+        none *= unseen.saturating_sub(wanted + i) as f64 / (unseen - i) as f64;
+    }
+    1.0 - none
+}
+
+impl Player {
+    /// The most the next card played from hand could add to the pile's score, and the top
+    /// country it would leave
+    fn best_play(&self) -> (u32, Option<&Country>) {
+        let mut best = (0, self.top_country());
+        for card in &self.hand {
+            let play = match card {
+                Card::Country(country) if self.can_play_country(country).is_ok() => {
+                    (u32::from(country.score), Some(country))
+                }
+                Card::Bonus(bonus) if self.can_play_bonus(bonus).is_ok() => {
+                    let top = self.top_country();
+                    (top.map_or(0, |top| u32::from(top.score)), top)
+                }
+                _ => continue,
+            };
+            if play.0 > best.0 {
+                best = play;
+            }
+        }
+        best
+    }
+}
+
+impl Board {
+    /// The cards `seat` hasn't seen: the deck the game was dealt from, less every pile, the
+    /// discard pile, the hazards in play and the seat's own hand
+    pub(crate) fn unseen_cards(&self, seat: usize) -> Vec<Card> {
+        let mut seen: Vec<Card> = self.past.clone();
+        for player in &self.players {
+            for country in &player.pile {
+                seen.extend(country.bonus.iter().map(|bonus| Card::Bonus(*bonus)));
+                seen.push(Card::Country(country.clone()));
+            }
+            seen.extend(
+                player
+                    .status
+                    .hazards
+                    .iter()
+                    .map(|hazard| Card::Grey(hazard.card)),
+            );
+        }
+        seen.extend(self.players[seat].hand.iter().cloned());
+
+        let mut unseen = self.deck.cards().to_vec();
+        for card in &seen {
+            if let Some(i) = unseen.iter().position(|other| other == card) {
+                unseen.swap_remove(i);
+            }
+        }
+        unseen.sort();
+        unseen
+    }
+
+    /// Weigh up going home now against playing on, using only what `seat` can see, or
+    /// `None` if there's no such seat.
+    ///
+    /// Playing on is taken to mean making the play that adds most to the pile, or getting
+    /// rid of a grey card if one is held, then going home as soon as possible. Each grey
+    /// card held or drawn puts that off by two turns, one to play it and one to miss. A
+    /// useful bonus drawn is played first if there's a turn to spare. Whether there's time
+    /// for all that is weighed against every turn left before the deck runs out, taking the
+    /// other seats to use one card a turn, but only the draw at the end of this turn is
+    /// looked at for Missed Flights and bonuses, which are taken to turn up independently.
+    pub fn go_home_risk(&self, seat: usize) -> Option<GoHomeRisk> {
+        let player = self.players.get(seat)?;
+        let unseen = self.unseen_cards(seat);
+
+        let mut next_draw: Vec<(Card, f64)> = Vec::new();
+        for card in &unseen {
+            match next_draw.last_mut() {
+                Some((last, count)) if last == card => *count += 1.0,
+                _ => next_draw.push((card.clone(), 1.0)),
+            }
+        }
+        for (_, chance) in &mut next_draw {
+            *chance /= unseen.len() as f64;
+        }
+        next_draw.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let greys_held = player
+            .hand
+            .iter()
+            .filter(|card| matches!(card, Card::Grey(_)))
+            .count();
+        let (gain, top) = if greys_held == 0 {
+            player.best_play()
+        } else {
+            (0, player.top_country())
+        };
+
+        // One card is played or discarded, then the hand is drawn back up
+        let draws = (HAND_SIZE + 1)
+            .saturating_sub(player.hand.len())
+            .min(self.future.len());
+        // Each round everyone else uses a card too
+        let turns_left = self.future.len().saturating_sub(draws) / self.players.len();
+
+        let flights = unseen
+            .iter()
+            .filter(|card| matches!(card, Card::Grey(GreyType::MissedFlight)))
+            .count();
+        let bonuses = unseen
+            .iter()
+            .filter(|card| match (card, top) {
+                (Card::Bonus(bonus), Some(top)) => top.allowed_bonus.contains(bonus.unparse()),
+                _ => false,
+            })
+            .count();
+        let missed_flight = chance_of_any(flights, unseen.len(), draws);
+        let useful_bonus = chance_of_any(bonuses, unseen.len(), draws);
+
+        // Turns until going home, if nothing bad is drawn
+        let wait = (2 * greys_held).max(1);
+        let pile = f64::from(player.trip_score() + gain);
+        let in_time = |turns: usize| if turns <= turns_left { 1.0 } else { 0.0 };
+        let bonus = top.map_or(0.0, |top| f64::from(top.score));
+
+        let clear = pile + useful_bonus * bonus * in_time(wait + 1);
+        let delayed = pile * in_time(wait + 2);
+        let play_on = in_time(wait) * ((1.0 - missed_flight) * clear + missed_flight * delayed);
+
+        Some(GoHomeRisk {
+            home_now: player.can_go_home().ok().map(|()| player.trip_score()),
+            play_on,
+            turns_left,
+            unseen: unseen.len(),
+            draws,
+            missed_flight,
+            useful_bonus,
+            next_draw,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Deck, Rules};
+
+    fn assert_near(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-12,
+            "{} isn't {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn one_draw_is_as_likely_as_the_share_of_wanted_cards() {
+        assert_near(chance_of_any(1, 10, 1), 0.1);
+        assert_near(chance_of_any(3, 4, 1), 0.75);
+    }
+
+    #[test]
+    fn several_draws_miss_every_wanted_card_together() {
+        // 1 - 7/10 * 6/9
+        assert_near(chance_of_any(3, 10, 2), 8.0 / 15.0);
+    }
+
+    #[test]
+    fn nothing_wanted_is_never_drawn() {
+        assert_near(chance_of_any(0, 10, 3), 0.0);
+    }
+
+    #[test]
+    fn drawing_every_card_finds_any_wanted_one() {
+        assert_near(chance_of_any(1, 2, 5), 1.0);
+    }
+
+    #[test]
+    fn a_deck_with_no_grey_cards_or_bonuses_risks_nothing() {
+        let deck: Deck = "30x country France 4 b".parse().unwrap();
+        let board = Board::new_game_with_deck(2, Rules::default(), deck, 3).unwrap();
+        let risk = board.go_home_risk(0).unwrap();
+        assert_eq!(risk.missed_flight, 0.0);
+        assert_eq!(risk.useful_bonus, 0.0);
+        assert_eq!(risk.unseen, 30 - HAND_SIZE);
+    }
+
+    #[test]
+    fn there_is_no_risk_for_a_missing_seat() {
+        let board = Board::new_seeded_game(2, Rules::default(), 3).unwrap();
+        assert!(board.go_home_risk(2).is_none());
+    }
+}
@@ -0,0 +1,334 @@
+use std::{
+    collections::HashMap,
+    io,
+    net::SocketAddr,
+    sync::{Arc, Condvar, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use tiny_http::{Header, Method, Request, Response};
+
+use crate::{
+    Rules,
+    session::{Session, SessionError, TurnCommand},
+};
+
+// The longest an events request is held open waiting for something to happen
+const MAX_WAIT: Duration = Duration::from_secs(60);
+
+/// Plays games for clients over HTTP, with JSON request and response bodies.
+///
+/// Seats are numbered from 0, as in events. Requests for a seat need the token returned when
+/// joining it, sent as `Authorization: Bearer <token>`.
+///
+/// | Request | Body | Response |
+/// |---|---|---|
+/// | `POST /games` | `{"players": 2, "seed": 1, "rules": {..}, "bots": [null, "greedy"]}`, all optional | `{"game": 1}` |
+/// | `POST /games/{game}/seats/{seat}` | `{"name": "Ann"}`, optional | `{"seat": 0, "token": ".."}` |
+/// | `GET /games/{game}/seats/{seat}` | | What the seat can see, including its hand |
+/// | `GET /games/{game}/seats/{seat}/moves` | | `{"phase": "move", "legal_moves": [..]}` |
+/// | `POST /games/{game}/seats/{seat}/moves` | A move like `{"command": "play", "index": 0}`, or `{"command": "draw", "from": "deck"}` | What the seat can see |
+/// | `GET /games/{game}/events?since=0&wait=30` | | `{"events": [..], "next": 3, "over": false}` |
+///
+/// An events request waits up to `wait` seconds for an event after the first `since`.
+/// Errors come back as `{"error": ".."}` with a 4xx status.
+pub struct GameServer {
+    server: tiny_http::Server,
+    lobby: Arc<Lobby>,
+}
+
+#[derive(Default)]
+struct Lobby {
+    games: Mutex<Games>,
+    // Woken whenever any game changes, for requests waiting on events
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct Games {
+    games: HashMap<u64, Game>,
+    next_id: u64,
+}
+
+struct Game {
+    session: Session,
+    // The secret each seat's player sends with their requests, once someone has joined it
+    tokens: Vec<Option<String>>,
+}
+
+struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<SessionError> for ApiError {
+    fn from(e: SessionError) -> Self {
+        let status = match e {
+            SessionError::Rejected(_) => 422,
+            _ => 409,
+        };
+        Self::new(status, e.to_string())
+    }
+}
+
+type ApiResult = Result<serde_json::Value, ApiError>;
+
+#[derive(Deserialize)]
+#[serde(default)]
+struct NewGame {
+    players: usize,
+    seed: Option<u64>,
+    rules: Rules,
+    bots: Vec<Option<String>>,
+}
+
+impl Default for NewGame {
+    fn default() -> Self {
+        Self {
+            players: 2,
+            seed: None,
+            rules: Rules::default(),
+            bots: Vec::new(),
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Join {
+    name: Option<String>,
+}
+
+/// The body of a request to a seat
+enum Body {
+    Join(Join),
+    Move(TurnCommand),
+    Empty,
+}
+
+fn to_json(view: impl Serialize) -> serde_json::Value {
+    serde_json::to_value(view).expect("Views always serialize")
+}
+
+impl Game {
+    fn new(request: NewGame) -> Result<Self, ApiError> {
+        let seed = request.seed.unwrap_or_else(rand::random);
+        let session = Session::new(request.players, request.rules, seed, &request.bots)
+            .map_err(|e| ApiError::new(400, e.to_string()))?;
+        Ok(Self {
+            tokens: vec![None; session.players()],
+            session,
+        })
+    }
+
+    fn join(&mut self, seat: usize, join: Join) -> ApiResult {
+        if self.session.is_bot(seat) {
+            return Err(ApiError::new(
+                409,
+                format!("seat {} is played by a bot", seat),
+            ));
+        }
+        if self.tokens[seat].is_some() {
+            return Err(ApiError::new(409, format!("seat {} is taken", seat)));
+        }
+
+        let token = format!("{:016x}", rand::random::<u64>());
+        self.tokens[seat] = Some(token.clone());
+        if let Some(name) = join.name {
+            self.session.set_name(seat, name);
+        }
+        Ok(serde_json::json!({ "seat": seat, "token": token }))
+    }
+
+    /// Check `request` comes from whoever joined `seat`
+    fn authorize(&self, seat: usize, request: &Request) -> Result<(), ApiError> {
+        let token = request
+            .headers()
+            .iter()
+            .find(|header| header.field.equiv("Authorization"))
+            .and_then(|header| header.value.as_str().strip_prefix("Bearer "));
+        match (&self.tokens[seat], token) {
+            (Some(expected), Some(token)) if expected == token => Ok(()),
+            (None, _) => Err(ApiError::new(
+                403,
+                format!("nobody has joined seat {}", seat),
+            )),
+            _ => Err(ApiError::new(403, format!("wrong token for seat {}", seat))),
+        }
+    }
+}
+
+fn parse_body<T: DeserializeOwned + Default>(request: &mut Request) -> Result<T, ApiError> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .read_to_string(&mut body)
+        .map_err(|e| ApiError::new(400, e.to_string()))?;
+    if body.trim().is_empty() {
+        return Ok(T::default());
+    }
+    serde_json::from_str(&body).map_err(|e| ApiError::new(400, e.to_string()))
+}
+
+/// The value of `name` in the query string of `url`
+fn query<'a>(url: &'a str, name: &str) -> Option<&'a str> {
+    let (_, query) = url.split_once('?')?;
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+fn query_number<T: std::str::FromStr>(url: &str, name: &str, default: T) -> Result<T, ApiError> {
+    match query(url, name) {
+        Some(value) => value
+            .parse()
+            .map_err(|_| ApiError::new(400, format!("`{}` should be a number", name))),
+        None => Ok(default),
+    }
+}
+
+impl Lobby {
+    fn handle(&self, request: &mut Request) -> ApiResult {
+        let url = request.url().to_string();
+        let path = url.split('?').next().unwrap_or_default();
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let method = request.method().clone();
+
+        if let (Method::Post, ["games"]) = (&method, &segments[..]) {
+            let game = Game::new(parse_body(request)?)?;
+            let mut games = self.games.lock().unwrap();
+            games.next_id += 1;
+            let id = games.next_id;
+            games.games.insert(id, game);
+            return Ok(serde_json::json!({ "game": id }));
+        }
+
+        let ["games", id, rest @ ..] = &segments[..] else {
+            return Err(ApiError::new(404, format!("no such path `{}`", path)));
+        };
+        let id: u64 = id
+            .parse()
+            .map_err(|_| ApiError::new(404, format!("no game `{}`", id)))?;
+
+        if let (Method::Get, ["events"]) = (&method, rest) {
+            let since = query_number(&url, "since", 0)?;
+            let wait = query_number(&url, "wait", 30)?;
+            return self.events(id, since, Duration::from_secs(wait).min(MAX_WAIT));
+        }
+
+        let ["seats", seat, rest @ ..] = rest else {
+            return Err(ApiError::new(404, format!("no such path `{}`", path)));
+        };
+        // Read before taking the lock, so a slow client can't hold up every game
+        let body = match (&method, rest) {
+            (Method::Post, []) => Body::Join(parse_body(request)?),
+            (Method::Post, ["moves"]) => Body::Move(
+                parse_body::<Option<TurnCommand>>(request)?
+                    .ok_or_else(|| ApiError::new(400, "expected a move"))?,
+            ),
+            _ => Body::Empty,
+        };
+
+        let mut games = self.games.lock().unwrap();
+        let game = games
+            .games
+            .get_mut(&id)
+            .ok_or_else(|| ApiError::new(404, format!("no game {}", id)))?;
+        let seat: usize = seat
+            .parse()
+            .ok()
+            .filter(|&seat| seat < game.tokens.len())
+            .ok_or_else(|| ApiError::new(404, format!("no seat `{}`", seat)))?;
+
+        match (&method, rest, body) {
+            (_, _, Body::Join(join)) => game.join(seat, join),
+            (Method::Get, [], _) => {
+                game.authorize(seat, request)?;
+                Ok(to_json(game.session.view(seat)))
+            }
+            (Method::Get, ["moves"], _) => {
+                game.authorize(seat, request)?;
+                Ok(to_json(game.session.moves(seat)))
+            }
+            (_, _, Body::Move(command)) => {
+                game.authorize(seat, request)?;
+                game.session.play(seat, command)?;
+                self.changed.notify_all();
+                Ok(to_json(game.session.view(seat)))
+            }
+            _ => Err(ApiError::new(404, format!("no such path `{}`", path))),
+        }
+    }
+
+    /// The events after the first `since`, waiting up to `wait` for there to be some
+    fn events(&self, id: u64, since: usize, wait: Duration) -> ApiResult {
+        let deadline = Instant::now() + wait;
+        let mut games = self.games.lock().unwrap();
+        loop {
+            let game = games
+                .games
+                .get(&id)
+                .ok_or_else(|| ApiError::new(404, format!("no game {}", id)))?;
+            let board = game.session.board();
+            let now = Instant::now();
+            if board.events().len() > since || board.is_over() || now >= deadline {
+                return Ok(to_json(game.session.events(since)));
+            }
+            games = self.changed.wait_timeout(games, deadline - now).unwrap().0;
+        }
+    }
+}
+
+impl GameServer {
+    /// Start listening on `address`, like `127.0.0.1:8080`. Port 0 picks any free port,
+    /// which `address` then reports.
+    pub fn bind(address: &str) -> io::Result<Self> {
+        let server = tiny_http::Server::http(address).map_err(io::Error::other)?;
+        Ok(Self {
+            server,
+            lobby: Arc::default(),
+        })
+    }
+
+    /// Where the server is listening
+    pub fn address(&self) -> SocketAddr {
+        self.server
+            .server_addr()
+            .to_ip()
+            .expect("The server always listens on TCP")
+    }
+
+    /// Answer requests until the process ends, each on its own thread so waiting for events
+    /// doesn't hold up other requests
+    pub fn run(self) {
+        for mut request in self.server.incoming_requests() {
+            let lobby = self.lobby.clone();
+            thread::spawn(move || {
+                let (status, body) = match lobby.handle(&mut request) {
+                    Ok(body) => (200, body),
+                    Err(e) => (e.status, serde_json::json!({ "error": e.message })),
+                };
+                let content_type = Header::from_bytes("Content-Type", "application/json")
+                    .expect("The header is valid");
+                let response = Response::from_string(body.to_string())
+                    .with_status_code(status)
+                    .with_header(content_type);
+                // The client may have gone away, and there's nobody else to tell
+                let _ = request.respond(response);
+            });
+        }
+    }
+}
@@ -0,0 +1,313 @@
+use std::{error::Error, fmt};
+
+use serde::{Deserialize, Serialize, Serializer, ser::SerializeStruct};
+
+use crate::{
+    BError, Board, Bonus, Card, Country, Event, Move, Rules, StatusType, Strategy, strategy,
+};
+
+impl Serialize for Country {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let allowed_bonus: Vec<Bonus> = self
+            .allowed_bonus
+            .chars()
+            .map(|c| Bonus::parse(&c))
+            .collect();
+
+        let mut country = serializer.serialize_struct("Country", 5)?;
+        country.serialize_field("name", &self.name)?;
+        country.serialize_field("continent", &self.continent())?;
+        country.serialize_field("score", &self.score)?;
+        country.serialize_field("allowed_bonus", &allowed_bonus)?;
+        country.serialize_field("bonus", &self.bonus)?;
+        country.end()
+    }
+}
+
+/// Everything the other players can see of a seat
+#[derive(Serialize)]
+pub(crate) struct PlayerState<'a> {
+    name: &'a str,
+    score: u32,
+    trip_score: u32,
+    pile: &'a [Country],
+    hazards: Vec<HazardState>,
+}
+
+#[derive(Serialize)]
+struct HazardState {
+    card: Card,
+    gos_left: Option<u8>,
+}
+
+impl Board {
+    /// Everything `seat` can see while the current player is in `phase`
+    pub(crate) fn seat_view(&self, seat: usize, phase: Phase) -> SeatView<'_> {
+        SeatView {
+            seat,
+            turn: self.turn,
+            phase,
+            hand: &self.players[seat].hand,
+            discard: self.top_discard(),
+            discard_size: self.past.len(),
+            deck: self.future.len(),
+            players: self.player_states(),
+            events: self.events().len(),
+        }
+    }
+
+    pub(crate) fn player_states(&self) -> Vec<PlayerState<'_>> {
+        self.players
+            .iter()
+            .map(|player| PlayerState {
+                name: &player.name,
+                score: player.score,
+                trip_score: player.trip_score(),
+                pile: &player.pile,
+                hazards: player
+                    .status
+                    .hazards
+                    .iter()
+                    .map(|hazard| HazardState {
+                        card: Card::Grey(hazard.card),
+                        gos_left: match hazard.status {
+                            StatusType::MissGo(gos) => Some(gos),
+                            _ => None,
+                        },
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum DrawCommand {
+    Draw { from: DrawPile },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DrawPile {
+    Deck,
+    Discard,
+}
+
+/// Anything a player can send on their turn: a move like `{"command": "play", "index": 0}`,
+/// or `{"command": "draw", "from": "deck"}` once they've moved
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum TurnCommand {
+    Move(Move),
+    Draw(DrawCommand),
+}
+
+/// What the current player has still to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Move,
+    Draw,
+    GameOver,
+}
+
+/// Why a command was turned down
+#[derive(Debug)]
+pub enum SessionError {
+    GameOver,
+    NotYourTurn { turn: usize },
+    MoveFirst,
+    DrawNext,
+    Rejected(BError),
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GameOver => write!(f, "the game is over"),
+            Self::NotYourTurn { turn } => write!(f, "it's seat {}'s turn", turn),
+            Self::MoveFirst => write!(f, "make a move first"),
+            Self::DrawNext => write!(f, "choose where to draw from"),
+            Self::Rejected(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for SessionError {}
+
+/// Everything one seat can see
+#[derive(Serialize)]
+pub struct SeatView<'a> {
+    seat: usize,
+    turn: usize,
+    phase: Phase,
+    hand: &'a [Card],
+    discard: Option<&'a Card>,
+    discard_size: usize,
+    deck: usize,
+    players: Vec<PlayerState<'a>>,
+    // How many events there have been, to ask for the ones after
+    events: usize,
+}
+
+/// What a seat can do now. Only the current seat has moves, and only before it has moved.
+#[derive(Serialize)]
+pub struct MovesView {
+    phase: Phase,
+    legal_moves: Vec<Move>,
+}
+
+impl MovesView {
+    pub fn legal_moves(&self) -> &[Move] {
+        &self.legal_moves
+    }
+}
+
+#[derive(Serialize)]
+pub struct EventsView<'a> {
+    events: &'a [Event],
+    next: usize,
+    over: bool,
+}
+
+/// A game played one command at a time by callers outside the crate, like HTTP clients or a
+/// browser, with bots taking their own turns in between
+#[derive(Clone)]
+pub struct Session {
+    board: Board,
+    bots: Vec<Option<Box<dyn Strategy>>>,
+    // The current player has moved and has still to say where to draw from
+    drawing: bool,
+}
+
+impl Session {
+    /// Deal a game for `players` seats, with a bot in each seat `bots` names a strategy for
+    pub fn new(
+        players: usize,
+        rules: Rules,
+        seed: u64,
+        bots: &[Option<String>],
+    ) -> Result<Self, BError> {
+        let board = Board::new_seeded_game(players, rules, seed)?;
+        if bots.len() > players {
+            return Err(BError::Custom("more bots than seats".to_string()));
+        }
+
+        let bots = (0..players)
+            .map(|seat| match bots.get(seat).cloned().flatten() {
+                Some(name) => strategy(&name, seed.wrapping_add(seat as u64))
+                    .map(Some)
+                    .ok_or_else(|| BError::Custom(format!("unknown strategy `{}`", name))),
+                None => Ok(None),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let mut session = Self {
+            board,
+            bots,
+            drawing: false,
+        };
+        session.advance();
+        Ok(session)
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Rename the player in `seat`
+    pub fn set_name(&mut self, seat: usize, name: String) {
+        let mut names = self.board.names();
+        names[seat] = name;
+        self.board.set_names(&names);
+    }
+
+    pub fn players(&self) -> usize {
+        self.bots.len()
+    }
+
+    pub fn is_bot(&self, seat: usize) -> bool {
+        self.bots[seat].is_some()
+    }
+
+    pub fn phase(&self) -> Phase {
+        if self.board.is_over() {
+            Phase::GameOver
+        } else if self.drawing {
+            Phase::Draw
+        } else {
+            Phase::Move
+        }
+    }
+
+    /// Play on until a person has something to decide, with bots taking their turns and
+    /// missed goes passed on
+    fn advance(&mut self) {
+        while !self.board.is_over() {
+            match self.bots[self.board.turn].as_deref_mut() {
+                Some(bot) => self.board.bot_turn(bot),
+                None if self.board.begin_turn() => return,
+                None => {}
+            }
+        }
+    }
+
+    pub fn view(&self, seat: usize) -> SeatView<'_> {
+        self.board.seat_view(seat, self.phase())
+    }
+
+    pub fn moves(&self, seat: usize) -> MovesView {
+        let legal_moves = if seat == self.board.turn && self.phase() == Phase::Move {
+            self.board.legal_moves()
+        } else {
+            Vec::new()
+        };
+        MovesView {
+            phase: self.phase(),
+            legal_moves,
+        }
+    }
+
+    /// The events after the first `since`
+    pub fn events(&self, since: usize) -> EventsView<'_> {
+        let events = self.board.events();
+        EventsView {
+            events: events.get(since..).unwrap_or_default(),
+            next: events.len(),
+            over: self.board.is_over(),
+        }
+    }
+
+    /// Carry out a command from the player in `seat`
+    pub fn play(&mut self, seat: usize, command: TurnCommand) -> Result<(), SessionError> {
+        if self.board.is_over() {
+            return Err(SessionError::GameOver);
+        }
+        if seat != self.board.turn {
+            return Err(SessionError::NotYourTurn {
+                turn: self.board.turn,
+            });
+        }
+
+        match (command, self.drawing) {
+            (TurnCommand::Move(mv), false) => {
+                self.board.apply_move(mv).map_err(SessionError::Rejected)?;
+                if self.board.can_take_discard() {
+                    self.drawing = true;
+                } else {
+                    self.board.end_turn(false);
+                    self.advance();
+                }
+            }
+            (TurnCommand::Draw(DrawCommand::Draw { from }), true) => {
+                self.drawing = false;
+                self.board.end_turn(matches!(from, DrawPile::Discard));
+                self.advance();
+            }
+            (_, false) => return Err(SessionError::MoveFirst),
+            (_, true) => return Err(SessionError::DrawNext),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use crate::{Board, Card, Event, HAND_SIZE, jsonl::Message};
+
+// A spectator who stops reading for this long is disconnected rather than holding up the game
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// People watching a game over TCP. Each connection is sent a line of JSON for every event
+/// and the public state after every turn, but never the cards in anyone's hand unless a
+/// delayed reveal is asked for.
+#[derive(Debug)]
+pub struct Spectators {
+    address: SocketAddr,
+    connections: Arc<Mutex<Vec<TcpStream>>>,
+    // The last public state sent, for anyone who connects part way through a game
+    latest: Arc<Mutex<Option<String>>>,
+    reveal_delay: Option<usize>,
+    // Everyone's hand at the end of each turn that hasn't been revealed yet, oldest first
+    hands: VecDeque<Vec<Vec<Card>>>,
+    turns: usize,
+}
+
+impl Spectators {
+    /// Start accepting spectators on `address`, like `127.0.0.1:7878`. Port 0 picks any
+    /// free port, which `address` then reports.
+    pub fn listen(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let connections: Arc<Mutex<Vec<TcpStream>>> = Arc::default();
+        let latest: Arc<Mutex<Option<String>>> = Arc::default();
+
+        let (accepted, state) = (connections.clone(), latest.clone());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else {
+                    continue;
+                };
+                if stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_err() {
+                    continue;
+                }
+                // Hold the state lock while joining, so no turn is missed or sent twice
+                let state = state.lock().unwrap();
+                let caught_up = match &*state {
+                    Some(line) => writeln!(stream, "{}", line),
+                    None => Ok(()),
+                };
+                if caught_up.is_ok() {
+                    accepted.lock().unwrap().push(stream);
+                }
+            }
+        });
+
+        Ok(Self {
+            address,
+            connections,
+            latest,
+            reveal_delay: None,
+            hands: VecDeque::new(),
+            turns: 0,
+        })
+    }
+
+    /// Show spectators every hand `turns` turns after the fact, and all the rest once the
+    /// game is over. Without this, hands are never shown.
+    pub fn with_reveal_delay(mut self, turns: Option<usize>) -> Self {
+        self.reveal_delay = turns;
+        self
+    }
+
+    /// Where spectators can connect
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// How many spectators are watching
+    pub fn count(&self) -> usize {
+        self.connections.lock().unwrap().len()
+    }
+
+    /// Send `line` to every spectator, dropping any that have gone away
+    fn broadcast(&self, line: &str) {
+        self.connections
+            .lock()
+            .unwrap()
+            .retain_mut(|stream| writeln!(stream, "{}", line).is_ok());
+    }
+
+    fn send(&self, message: &Message) {
+        self.broadcast(&serde_json::to_string(message).expect("Messages always serialize"));
+    }
+
+    /// Send the public state, keeping it for anyone who connects later
+    fn send_state(&self, message: &Message) {
+        let line = serde_json::to_string(message).expect("Messages always serialize");
+        let mut latest = self.latest.lock().unwrap();
+        self.broadcast(&line);
+        *latest = Some(line);
+    }
+
+    /// Send the oldest unrevealed hands
+    fn reveal_next(&mut self) {
+        if let Some(hands) = self.hands.pop_front() {
+            let turn = self.turns - self.hands.len();
+            self.send(&Message::Reveal {
+                turn,
+                hands: &hands,
+            });
+        }
+    }
+}
+
+impl Board {
+    /// Let people watch this game, or stop them with `None`
+    pub fn set_spectators(&mut self, spectators: Option<Spectators>) {
+        self.spectators = spectators;
+        if let Some(spectators) = &mut self.spectators {
+            spectators.hands.clear();
+            spectators.turns = 0;
+        }
+        if let Some(spectators) = &self.spectators {
+            spectators.send(&Message::GameStarted {
+                names: self.names(),
+                hand_size: HAND_SIZE,
+                deck: self.future.len(),
+            });
+            spectators.send_state(&self.public_state());
+        }
+    }
+
+    /// Remove the spectators, to carry them on to another game
+    pub fn take_spectators(&mut self) -> Option<Spectators> {
+        self.spectators.take()
+    }
+
+    fn public_state(&self) -> Message<'_> {
+        Message::PublicState {
+            seat: self.turn,
+            discard: self.top_discard(),
+            discard_size: self.past.len(),
+            deck: self.future.len(),
+            players: self.player_states(),
+        }
+    }
+
+    /// Pass an event on to any spectators
+    pub(crate) fn spectate_event(&self, event: &Event) {
+        if let Some(spectators) = &self.spectators {
+            spectators.send(&Message::Event(event));
+        }
+    }
+
+    /// Show spectators the state at the end of a turn, and any hands now due to be revealed.
+    /// Once the game is over, finished or abandoned, every hand is revealed.
+    pub(crate) fn spectate_turn(&mut self) {
+        let Some(mut spectators) = self.spectators.take() else {
+            return;
+        };
+        spectators.send_state(&self.public_state());
+
+        spectators.turns += 1;
+        if let Some(delay) = spectators.reveal_delay {
+            let hands = self.players.iter().map(|player| player.hand.clone());
+            spectators.hands.push_back(hands.collect());
+            if spectators.hands.len() > delay {
+                spectators.reveal_next();
+            }
+        }
+
+        if self.is_over() {
+            while !spectators.hands.is_empty() {
+                spectators.reveal_next();
+            }
+            spectators.send(&Message::GameOver {
+                scores: self.scores(),
+                winners: self.winners(),
+            });
+        }
+        self.spectators = Some(spectators);
+    }
+}
@@ -0,0 +1,312 @@
+use std::{
+    env, fs,
+    io::{self, IsTerminal},
+    path::Path,
+    str::FromStr,
+    sync::{
+        RwLock,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use colored::{Color, ColoredString, Colorize};
+
+use crate::Continent;
+
+static THEME: RwLock<Theme> = RwLock::new(DARK);
+static TAGS: AtomicBool = AtomicBool::new(false);
+
+/// Show continent tags on countries and good/bad markers on advice, so cards
+/// can be told apart without relying on colour
+pub fn set_tags(enabled: bool) {
+    TAGS.store(enabled, Ordering::Relaxed);
+}
+
+pub(crate) fn tags() -> bool {
+    TAGS.load(Ordering::Relaxed)
+}
+
+/// Force colour on or off
+pub fn set_colour(enabled: bool) {
+    colored::control::set_override(enabled);
+}
+
+/// Turn colour off when `NO_COLOR` is set or stdout isn't a terminal.
+/// Without colour, continent tags are turned on instead.
+pub fn detect_colour() {
+    let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    if no_color || !io::stdout().is_terminal() {
+        set_colour(false);
+        set_tags(true);
+    }
+}
+
+/// A colour and text style to draw something in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Paint {
+    colour: Color,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    dimmed: bool,
+}
+
+impl Paint {
+    const fn new(colour: Color) -> Self {
+        Self {
+            colour,
+            bold: false,
+            italic: false,
+            underline: false,
+            dimmed: false,
+        }
+    }
+
+    const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::new(Color::TrueColor { r, g, b })
+    }
+
+    pub fn paint(&self, text: &str) -> ColoredString {
+        let mut painted = text.color(self.colour);
+        if self.bold {
+            painted = painted.bold();
+        }
+        if self.italic {
+            painted = painted.italic();
+        }
+        if self.underline {
+            painted = painted.underline();
+        }
+        if self.dimmed {
+            painted = painted.dimmed();
+        }
+        painted
+    }
+}
+
+impl FromStr for Paint {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut words = value.split_whitespace();
+        let colour = words.next().ok_or("missing colour")?;
+        let mut paint = Self::new(parse_colour(colour)?);
+
+        for style in words {
+            match style {
+                "bold" => paint.bold = true,
+                "italic" => paint.italic = true,
+                "underline" => paint.underline = true,
+                "dimmed" => paint.dimmed = true,
+                _ => return Err(format!("unknown style `{}`", style)),
+            }
+        }
+
+        Ok(paint)
+    }
+}
+
+fn parse_colour(colour: &str) -> Result<Color, String> {
+    let invalid = || format!("invalid colour `{}`", colour);
+
+    if let Some(hex) = colour.strip_prefix('#') {
+        if hex.len() != 6 || !hex.is_ascii() {
+            return Err(invalid());
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+        return Ok(Color::TrueColor {
+            r: channel(0)?,
+            g: channel(2)?,
+            b: channel(4)?,
+        });
+    }
+
+    if colour.contains(',') {
+        let channels: Vec<u8> = colour
+            .split(',')
+            .map(|channel| channel.trim().parse().map_err(|_| invalid()))
+            .collect::<Result<_, _>>()?;
+        return match channels[..] {
+            [r, g, b] => Ok(Color::TrueColor { r, g, b }),
+            _ => Err(invalid()),
+        };
+    }
+
+    Color::from_str(colour).map_err(|_| invalid())
+}
+
+/// The colours and styles every kind of card is drawn in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub(crate) africa: Paint,
+    pub(crate) america: Paint,
+    pub(crate) antarctica: Paint,
+    pub(crate) asia: Paint,
+    pub(crate) europe: Paint,
+    pub(crate) oceania: Paint,
+    pub(crate) bonus: Paint,
+    pub(crate) money: Paint,
+    // Marker and name paints for good and bad advice
+    pub(crate) good: (Paint, Paint),
+    pub(crate) bad: (Paint, Paint),
+    pub(crate) grey: Paint,
+    pub(crate) special: Paint,
+}
+
+impl Theme {
+    /// The theme all cards are currently drawn with
+    pub fn current() -> Self {
+        *THEME.read().unwrap()
+    }
+
+    /// Draw all cards with this theme from now on
+    pub fn set(self) {
+        *THEME.write().unwrap() = self;
+    }
+
+    /// A theme that comes with the game: `dark`, `light` or `colour-blind`
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(DARK),
+            "light" => Some(LIGHT),
+            "colour-blind" | "color-blind" => Some(COLOUR_BLIND),
+            _ => None,
+        }
+    }
+
+    /// Names of the themes that come with the game
+    pub fn builtin_names() -> &'static [&'static str] {
+        &["dark", "light", "colour-blind"]
+    }
+
+    /// Read a theme file, reporting the first line that can't be understood.
+    ///
+    /// Each line is `key = colour [style ...]`, where the key is one of `africa`, `america`,
+    /// `antarctica`, `asia`, `europe`, `oceania`, `bonus`, `money`, `good`, `good_name`,
+    /// `bad`, `bad_name`, `grey` or `special`. Colours are `#rrggbb`, `r,g,b` or a terminal
+    /// colour name like `red`, and styles are `bold`, `italic`, `underline` and `dimmed`.
+    /// `base = <built-in theme>` picks where any missing keys come from, `dark` by default.
+    /// Lines starting with `#` are comments.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let with_path = |e| format!("{}: {}", path.display(), e);
+        let contents = fs::read_to_string(path).map_err(|e| with_path(e.to_string()))?;
+        contents.parse().map_err(with_path)
+    }
+
+    pub(crate) fn continent(&self, continent: Continent) -> Paint {
+        match continent {
+            Continent::Africa => self.africa,
+            Continent::America => self.america,
+            Continent::Antarctica => self.antarctica,
+            Continent::Asia => self.asia,
+            Continent::Europe => self.europe,
+            Continent::Oceania => self.oceania,
+        }
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        let mut lines = Vec::new();
+        let mut theme = DARK;
+
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected `key = value`", i + 1))?;
+            let (key, value) = (key.trim(), value.trim());
+
+            if key == "base" {
+                theme = Theme::builtin(value).ok_or_else(|| {
+                    format!(
+                        "line {}: unknown theme `{}`, expected one of {}",
+                        i + 1,
+                        value,
+                        Theme::builtin_names().join(", ")
+                    )
+                })?;
+            } else {
+                lines.push((i + 1, key, value));
+            }
+        }
+
+        for (line, key, value) in lines {
+            let paint: Paint = value.parse().map_err(|e| format!("line {}: {}", line, e))?;
+            let slot = match key {
+                "africa" => &mut theme.africa,
+                "america" => &mut theme.america,
+                "antarctica" => &mut theme.antarctica,
+                "asia" => &mut theme.asia,
+                "europe" => &mut theme.europe,
+                "oceania" => &mut theme.oceania,
+                "bonus" => &mut theme.bonus,
+                "money" => &mut theme.money,
+                "good" => &mut theme.good.0,
+                "good_name" => &mut theme.good.1,
+                "bad" => &mut theme.bad.0,
+                "bad_name" => &mut theme.bad.1,
+                "grey" => &mut theme.grey,
+                "special" => &mut theme.special,
+                _ => return Err(format!("line {}: unknown key `{}`", line, key)),
+            };
+            *slot = paint;
+        }
+
+        Ok(theme)
+    }
+}
+
+// Colours picked for a dark terminal background
+const DARK: Theme = Theme {
+    africa: Paint::rgb(134, 80, 29),
+    america: Paint::rgb(234, 83, 119),
+    antarctica: Paint::rgb(220, 220, 220),
+    asia: Paint::rgb(196, 181, 61),
+    europe: Paint::rgb(118, 72, 141),
+    oceania: Paint::rgb(113, 209, 164),
+    bonus: Paint::rgb(106, 229, 218),
+    money: Paint::new(Color::Green),
+    good: (Paint::rgb(12, 186, 74), Paint::rgb(81, 255, 143)),
+    bad: (Paint::rgb(248, 30, 88), Paint::rgb(248, 73, 119)),
+    grey: Paint::rgb(120, 120, 120),
+    special: Paint::rgb(231, 157, 72),
+};
+
+// Darker versions of the dark theme, readable on a white background
+const LIGHT: Theme = Theme {
+    africa: Paint::rgb(134, 80, 29),
+    america: Paint::rgb(190, 40, 80),
+    antarctica: Paint::rgb(90, 110, 130),
+    asia: Paint::rgb(150, 130, 0),
+    europe: Paint::rgb(118, 72, 141),
+    oceania: Paint::rgb(0, 130, 90),
+    bonus: Paint::rgb(0, 130, 140),
+    money: Paint::rgb(0, 120, 40),
+    good: (Paint::rgb(0, 130, 50), Paint::rgb(0, 100, 40)),
+    bad: (Paint::rgb(200, 0, 50), Paint::rgb(170, 30, 70)),
+    grey: Paint::rgb(110, 110, 110),
+    special: Paint::rgb(190, 100, 0),
+};
+
+// Based on the Okabe-Ito palette, with good and bad advice split into blue and orange, and
+// a slate for Antarctica that reads on dark and light backgrounds alike
+const COLOUR_BLIND: Theme = Theme {
+    africa: Paint::rgb(213, 94, 0),
+    america: Paint::rgb(204, 121, 167),
+    antarctica: Paint::rgb(110, 140, 170),
+    asia: Paint::rgb(240, 228, 66),
+    europe: Paint::rgb(0, 114, 178),
+    oceania: Paint::rgb(0, 158, 115),
+    bonus: Paint::rgb(86, 180, 233),
+    money: Paint::rgb(86, 180, 233),
+    good: (Paint::rgb(0, 114, 178), Paint::rgb(86, 180, 233)),
+    bad: (Paint::rgb(213, 94, 0), Paint::rgb(230, 159, 0)),
+    grey: Paint::rgb(153, 153, 153),
+    special: Paint::rgb(230, 159, 0),
+};
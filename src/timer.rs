@@ -0,0 +1,297 @@
+use std::{
+    fmt, io,
+    sync::{
+        Arc, Mutex, OnceLock,
+        mpsc::{self, Receiver, TryRecvError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{Board, Card, Event, Move, Strategy, locale::trf, replay::Action, strategy};
+
+// How often a timed prompt checks for input
+const TICK: Duration = Duration::from_millis(50);
+
+// Seconds left at which a timed prompt reminds the player of the time
+const REMINDERS: &[u64] = &[30, 10, 5];
+
+/// Where the turn timer gets the time from
+pub trait Clock: Send + Sync {
+    /// Time passed since some fixed starting point
+    fn now(&self) -> Duration;
+
+    /// Wait for `duration` to pass
+    fn sleep(&self, duration: Duration);
+}
+
+/// The real time
+#[derive(Debug)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// A clock that only moves when told to, so timeouts can be tested without waiting.
+/// Sleeping on it moves it on straight away.
+#[derive(Debug, Default)]
+pub struct FakeClock {
+    now: Mutex<Duration>,
+}
+
+impl FakeClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.now.lock().unwrap()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
+
+/// What happens for a player who runs out of time
+pub enum DefaultAction {
+    /// Discard the card worth the fewest points and draw from the deck
+    DiscardLowest,
+    /// Let a bot take the rest of the turn
+    Bot(Box<dyn Strategy>),
+}
+
+impl DefaultAction {
+    /// `discard`, or the name of a strategy. `seed` drives any randomness the strategy uses.
+    pub fn parse(name: &str, seed: u64) -> Option<Self> {
+        match name {
+            "discard" => Some(Self::DiscardLowest),
+            _ => strategy(name, seed).map(Self::Bot),
+        }
+    }
+}
+
+/// A time limit on each turn played at the terminal
+pub struct TurnTimer {
+    limit: Duration,
+    default: DefaultAction,
+    clock: Arc<dyn Clock>,
+    // Where answers come from, if not the terminal
+    input: Option<Mutex<Receiver<io::Result<String>>>>,
+    // When the current turn runs out, if one has started
+    deadline: Option<Duration>,
+}
+
+impl fmt::Debug for TurnTimer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TurnTimer")
+            .field("limit", &self.limit)
+            .field("deadline", &self.deadline)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TurnTimer {
+    pub fn new(limit: Duration, default: DefaultAction) -> Self {
+        Self {
+            limit,
+            default,
+            clock: Arc::new(SystemClock::default()),
+            input: None,
+            deadline: None,
+        }
+    }
+
+    /// Take the time from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Read answers from `lines` instead of the terminal. Dropping the sender counts as
+    /// stdin being closed.
+    pub fn with_input(mut self, lines: Receiver<io::Result<String>>) -> Self {
+        self.input = Some(Mutex::new(lines));
+        self
+    }
+
+    /// Start counting down a new turn
+    pub fn start(&mut self) {
+        self.deadline = Some(self.clock.now() + self.limit);
+    }
+
+    /// Time left in the current turn, or the whole limit if no turn has started
+    pub fn remaining(&self) -> Duration {
+        match self.deadline {
+            Some(deadline) => deadline.saturating_sub(self.clock.now()),
+            None => self.limit,
+        }
+    }
+
+    pub fn expired(&self) -> bool {
+        self.remaining().is_zero()
+    }
+
+    // Whole seconds left, rounded up so the last second reads as 1 rather than 0
+    fn seconds_left(&self) -> u64 {
+        let remaining = self.remaining();
+        remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0)
+    }
+
+    /// Wait for the next line of input, or `None` if the turn runs out first. Reminds the
+    /// player how long is left as the deadline gets closer. An empty string means the input
+    /// is closed.
+    pub fn read_line(&self) -> Option<io::Result<String>> {
+        let input = self
+            .input
+            .as_ref()
+            .unwrap_or_else(|| input())
+            .lock()
+            .unwrap();
+        let mut last_reminder = self.seconds_left();
+
+        loop {
+            let remaining = self.remaining();
+            if remaining.is_zero() {
+                return None;
+            }
+
+            let seconds = self.seconds_left();
+            if let Some(&reminder) = REMINDERS
+                .iter()
+                .find(|&&reminder| seconds <= reminder && reminder < last_reminder)
+            {
+                println!("{}", trf("timer.left", &[&seconds]));
+                last_reminder = reminder;
+            }
+
+            match input.try_recv() {
+                Ok(line) => return Some(line),
+                Err(TryRecvError::Empty) => self.clock.sleep(TICK.min(remaining)),
+                Err(TryRecvError::Disconnected) => return Some(Ok(String::new())),
+            }
+        }
+    }
+
+    /// The move made for a player who runs out of time
+    fn default_move(&mut self, board: &Board) -> Move {
+        let moves = board.legal_moves();
+        match &mut self.default {
+            DefaultAction::Bot(bot) => bot.choose_move(board, &moves),
+            DefaultAction::DiscardLowest => {
+                // Points each card could add to the pile, so advice and hazards go first
+                let player = &board.players[board.turn];
+                let lowest = (0..player.hand.len())
+                    .min_by_key(|&i| match &player.hand[i] {
+                        Card::Country(country) => country.score,
+                        Card::Bonus(_) => player.top_country().map_or(0, |top| top.score),
+                        _ => 0,
+                    })
+                    .expect("A player about to move has cards in hand");
+                Move::Discard(lowest)
+            }
+        }
+    }
+
+    /// Whether a player who runs out of time takes the top discard
+    fn default_draw(&mut self, board: &Board) -> bool {
+        match &mut self.default {
+            DefaultAction::Bot(bot) => board.can_take_discard() && bot.take_discard(board),
+            DefaultAction::DiscardLowest => false,
+        }
+    }
+}
+
+// Lines typed at the terminal, read on their own thread so a prompt can stop waiting for them
+fn input() -> &'static Mutex<Receiver<io::Result<String>>> {
+    static INPUT: OnceLock<Mutex<Receiver<io::Result<String>>>> = OnceLock::new();
+    INPUT.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            loop {
+                let mut buffer = String::new();
+                let line = io::stdin().read_line(&mut buffer).map(|_| buffer);
+                let end = matches!(&line, Ok(line) if line.is_empty());
+                if sender.send(line).is_err() || end {
+                    break;
+                }
+            }
+        });
+        Mutex::new(receiver)
+    })
+}
+
+/// Wait as long as it takes for the next line of input. An empty string means stdin is closed.
+pub(crate) fn read_line() -> io::Result<String> {
+    input()
+        .lock()
+        .unwrap()
+        .recv()
+        .unwrap_or_else(|_| Ok(String::new()))
+}
+
+impl Board {
+    /// Time each turn played at the terminal, or stop timing them with `None`
+    pub fn set_turn_timer(&mut self, timer: Option<TurnTimer>) {
+        self.timer = timer;
+    }
+
+    /// Remove the turn timer, to use it for another game
+    pub fn take_turn_timer(&mut self) -> Option<TurnTimer> {
+        self.timer.take()
+    }
+
+    /// Whether the current player has used up their time this turn
+    pub fn turn_timed_out(&self) -> bool {
+        self.timer.as_ref().is_some_and(TurnTimer::expired)
+    }
+
+    /// Finish the current player's turn for them with the timer's default action, making
+    /// their move if they haven't yet and then drawing. Does nothing without a timer.
+    pub fn time_out(&mut self) {
+        let Some(mut timer) = self.timer.take() else {
+            return;
+        };
+        self.emit(Event::TimedOut { seat: self.turn });
+
+        // Every turn ends with a draw, so a move as the last action was made this turn
+        if !matches!(self.actions.last(), Some(Action::Move(_))) {
+            let mv = timer.default_move(self);
+            self.apply_move(mv)
+                .expect("The default action only makes legal moves");
+        }
+        let from_discard = timer.default_draw(self);
+        self.end_turn(from_discard);
+
+        self.timer = Some(timer);
+    }
+
+    /// How long the current player has left, for showing with a prompt
+    pub(crate) fn time_left(&self) -> Option<String> {
+        let timer = self.timer.as_ref()?;
+        Some(trf("timer.left", &[&timer.seconds_left()]))
+    }
+}
@@ -0,0 +1,328 @@
+use std::{cmp::Ordering, fmt, fs, io, path::Path};
+
+use rand::{prelude::*, rngs::StdRng};
+use tabular::{Row, Table};
+
+use crate::{Board, Rules, bot};
+
+const BASE_RATING: f64 = 1500.0;
+
+/// A round-robin of two-player games between registered bot strategies
+pub struct Tournament {
+    strategies: Vec<String>,
+    games_per_pairing: usize,
+    seed: u64,
+    rules: Rules,
+}
+
+/// How one strategy did over a tournament
+#[derive(Debug)]
+pub struct Standing {
+    pub name: String,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    pub total_score: u64,
+    pub rating: f64,
+    /// Half-width of the 95% confidence interval on `rating`
+    pub rating_error: f64,
+}
+
+impl Standing {
+    pub fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+}
+
+/// The result of one game between two strategies
+struct Game {
+    first: usize,
+    second: usize,
+    // 1 for a win for `first`, 0 for a loss and 0.5 for a draw
+    first_result: f64,
+}
+
+/// Final standings of a tournament, best rated first
+pub struct Results {
+    pub standings: Vec<Standing>,
+}
+
+impl Tournament {
+    /// Set up a tournament between every registered strategy
+    pub fn new(games_per_pairing: usize, seed: u64, rules: Rules) -> Self {
+        Self {
+            strategies: bot::strategy_names()
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            games_per_pairing,
+            seed,
+            rules,
+        }
+    }
+
+    /// Only play these strategies, which must all be registered
+    pub fn with_strategies(mut self, strategies: Vec<String>) -> Self {
+        self.strategies = strategies;
+        self
+    }
+
+    /// Play every pairing, each strategy taking the first seat in half of its games
+    pub fn run(&self) -> Result<Results, String> {
+        for name in &self.strategies {
+            if bot::strategy(name, 0).is_none() {
+                return Err(format!("Unknown strategy: {}", name));
+            }
+        }
+
+        let mut seeds = StdRng::seed_from_u64(self.seed);
+        let mut games = Vec::new();
+        let mut standings: Vec<Standing> = self
+            .strategies
+            .iter()
+            .map(|name| Standing {
+                name: name.clone(),
+                wins: 0,
+                draws: 0,
+                losses: 0,
+                total_score: 0,
+                rating: BASE_RATING,
+                rating_error: 0.0,
+            })
+            .collect();
+
+        for a in 0..self.strategies.len() {
+            for b in (a + 1)..self.strategies.len() {
+                for game in 0..self.games_per_pairing {
+                    let (first, second) = if game % 2 == 0 { (a, b) } else { (b, a) };
+                    let seed: u64 = seeds.random();
+
+                    let scores = self.play_game(first, second, seed);
+                    standings[first].total_score += scores[0] as u64;
+                    standings[second].total_score += scores[1] as u64;
+
+                    let first_result = match scores[0].cmp(&scores[1]) {
+                        Ordering::Greater => {
+                            standings[first].wins += 1;
+                            standings[second].losses += 1;
+                            1.0
+                        }
+                        Ordering::Less => {
+                            standings[first].losses += 1;
+                            standings[second].wins += 1;
+                            0.0
+                        }
+                        Ordering::Equal => {
+                            standings[first].draws += 1;
+                            standings[second].draws += 1;
+                            0.5
+                        }
+                    };
+
+                    games.push(Game {
+                        first,
+                        second,
+                        first_result,
+                    });
+                }
+            }
+        }
+
+        rate(&mut standings, &games);
+        standings.sort_by(|a, b| b.rating.total_cmp(&a.rating));
+
+        Ok(Results { standings })
+    }
+
+    fn play_game(&self, first: usize, second: usize, seed: u64) -> Vec<u32> {
+        let mut bots = vec![
+            bot::strategy(&self.strategies[first], seed).unwrap(),
+            bot::strategy(&self.strategies[second], seed.wrapping_add(1)).unwrap(),
+        ];
+
+        let mut board = Board::new_seeded_game(2, self.rules.clone(), seed)
+            .expect("The standard deck has enough cards for two players");
+        board.set_debug_audit(true);
+        board.bot_game(&mut bots);
+        board.scores()
+    }
+}
+
+/// Expected score of a player rated `rating` against one rated `opponent`
+fn expected(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+/// Fit Elo ratings to the results by maximum likelihood, centred on `BASE_RATING`.
+///
+/// Every strategy also gets one virtual draw against a `BASE_RATING` opponent so that a
+/// strategy that wins (or loses) every game still gets a finite rating.
+fn rate(standings: &mut [Standing], games: &[Game]) {
+    let mut ratings = vec![BASE_RATING; standings.len()];
+
+    for _ in 0..1000 {
+        let mut actual = vec![0.5; ratings.len()];
+        let mut predicted: Vec<f64> = ratings.iter().map(|r| expected(*r, BASE_RATING)).collect();
+        let mut counts = vec![1.0; ratings.len()];
+
+        for game in games {
+            let first_expected = expected(ratings[game.first], ratings[game.second]);
+            actual[game.first] += game.first_result;
+            actual[game.second] += 1.0 - game.first_result;
+            predicted[game.first] += first_expected;
+            predicted[game.second] += 1.0 - first_expected;
+            counts[game.first] += 1.0;
+            counts[game.second] += 1.0;
+        }
+
+        let mut largest_step: f64 = 0.0;
+        for i in 0..ratings.len() {
+            let step = 800.0 * (actual[i] - predicted[i]) / counts[i];
+            ratings[i] += step;
+            largest_step = largest_step.max(step.abs());
+        }
+
+        let mean = ratings.iter().sum::<f64>() / ratings.len() as f64;
+        ratings.iter_mut().for_each(|r| *r += BASE_RATING - mean);
+
+        if largest_step < 0.01 {
+            break;
+        }
+    }
+
+    // The standard error comes from the Fisher information of each strategy's games
+    let scale = 400.0 / 10f64.ln();
+    for (i, standing) in standings.iter_mut().enumerate() {
+        let p = expected(ratings[i], BASE_RATING);
+        let mut information = p * (1.0 - p);
+        for game in games {
+            let opponent = if game.first == i {
+                game.second
+            } else if game.second == i {
+                game.first
+            } else {
+                continue;
+            };
+            let p = expected(ratings[i], ratings[opponent]);
+            information += p * (1.0 - p);
+        }
+
+        standing.rating = ratings[i];
+        standing.rating_error = 1.96 * scale / information.sqrt();
+    }
+}
+
+impl Results {
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+impl fmt::Display for Results {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut table = Table::new("{:>}  {:<}  {:>}  {:>}  {:>}  {:>}  {:>}  {:>}  {:>}");
+        table.add_row(
+            Row::new()
+                .with_cell("#")
+                .with_cell("Strategy")
+                .with_cell("Games")
+                .with_cell("W")
+                .with_cell("D")
+                .with_cell("L")
+                .with_cell("Avg score")
+                .with_cell("Elo")
+                .with_cell("95%"),
+        );
+
+        for (i, standing) in self.standings.iter().enumerate() {
+            let games = standing.games().max(1);
+            table.add_row(
+                Row::new()
+                    .with_cell(i + 1)
+                    .with_cell(&standing.name)
+                    .with_cell(standing.games())
+                    .with_cell(standing.wins)
+                    .with_cell(standing.draws)
+                    .with_cell(standing.losses)
+                    .with_cell(format!(
+                        "{:.1}",
+                        standing.total_score as f64 / games as f64
+                    ))
+                    .with_cell(format!("{:.0}", standing.rating))
+                    .with_cell(format!("±{:.0}", standing.rating_error)),
+            );
+        }
+
+        write!(f, "{}", table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn standings(count: usize) -> Vec<Standing> {
+        (0..count)
+            .map(|i| Standing {
+                name: i.to_string(),
+                wins: 0,
+                draws: 0,
+                losses: 0,
+                total_score: 0,
+                rating: 0.0,
+                rating_error: 0.0,
+            })
+            .collect()
+    }
+
+    fn game(first: usize, second: usize, first_result: f64) -> Game {
+        Game {
+            first,
+            second,
+            first_result,
+        }
+    }
+
+    fn ratings(count: usize, games: &[Game]) -> Vec<f64> {
+        let mut standings = standings(count);
+        rate(&mut standings, games);
+        standings.iter().map(|standing| standing.rating).collect()
+    }
+
+    #[test]
+    fn a_strategy_that_wins_every_game_is_rated_highest() {
+        let games = [
+            game(0, 1, 1.0),
+            game(2, 0, 0.0),
+            game(1, 2, 1.0),
+            game(2, 1, 1.0),
+        ];
+        let ratings = ratings(3, &games);
+        assert!(ratings[0] > ratings[1] && ratings[0] > ratings[2]);
+    }
+
+    #[test]
+    fn equal_results_give_equal_ratings() {
+        let games = [game(0, 1, 1.0), game(1, 0, 1.0), game(0, 1, 0.5)];
+        let ratings = ratings(2, &games);
+        assert!((ratings[0] - ratings[1]).abs() < 1e-6);
+        assert!((ratings[0] - BASE_RATING).abs() < 1e-6);
+    }
+
+    #[test]
+    fn the_order_of_the_games_does_not_matter() {
+        let mut games = vec![
+            game(0, 1, 1.0),
+            game(1, 2, 0.5),
+            game(2, 0, 1.0),
+            game(0, 2, 1.0),
+            game(1, 0, 0.0),
+        ];
+        let forwards = ratings(3, &games);
+        games.reverse();
+        let backwards = ratings(3, &games);
+        for (forwards, backwards) in forwards.iter().zip(&backwards) {
+            assert!((forwards - backwards).abs() < 1e-6);
+        }
+    }
+}
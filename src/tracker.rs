@@ -0,0 +1,134 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{
+    Board, Card, Continent,
+    locale::{tr, trf},
+};
+
+static CARD_TRACKER: AtomicBool = AtomicBool::new(false);
+
+/// Show each player the cards they haven't seen yet at the start of their turn
+pub fn set_card_tracker(enabled: bool) {
+    CARD_TRACKER.store(enabled, Ordering::Relaxed);
+}
+
+pub(crate) fn card_tracker() -> bool {
+    CARD_TRACKER.load(Ordering::Relaxed)
+}
+
+/// A line of the tracker: countries by continent, everything else by type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Group {
+    Continent(Continent),
+    Bonus,
+    Advice,
+    Grey,
+    Special,
+}
+
+impl Group {
+    fn of(card: &Card) -> Self {
+        match card {
+            Card::Country(country) => Self::Continent(country.continent()),
+            Card::Bonus(_) => Self::Bonus,
+            Card::Advice(_) => Self::Advice,
+            Card::Grey(_) => Self::Grey,
+            Card::Special(_) => Self::Special,
+        }
+    }
+}
+
+impl Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Continent(continent) => continent.fmt(f),
+            Self::Bonus => write!(f, "{}", tr("tracker.bonus")),
+            Self::Advice => write!(f, "{}", tr("tracker.advice")),
+            Self::Grey => write!(f, "{}", tr("tracker.grey")),
+            Self::Special => write!(f, "{}", tr("tracker.special")),
+        }
+    }
+}
+
+impl Board {
+    /// The cards `seat` hasn't seen, with how many there are of each, grouped into lines
+    fn unseen_groups(&self, seat: usize) -> BTreeMap<Group, Vec<(Card, usize)>> {
+        let mut groups: BTreeMap<Group, Vec<(Card, usize)>> = BTreeMap::new();
+        for card in self.unseen_cards(seat) {
+            let group = groups.entry(Group::of(&card)).or_default();
+            match group.last_mut() {
+                Some((last, count)) if *last == card => *count += 1,
+                _ => group.push((card, 1)),
+            }
+        }
+        groups
+    }
+
+    /// The current player's unseen cards, as a panel of the turn heading
+    pub(crate) fn show_unseen(&self) {
+        let groups = self.unseen_groups(self.turn);
+        let total: usize = groups.values().flatten().map(|(_, count)| count).sum();
+
+        println!("{}", trf("tracker.heading", &[&total]));
+        for (group, cards) in &groups {
+            let cards: Vec<String> = cards
+                .iter()
+                .map(|(card, count)| match count {
+                    1 => card.to_string(),
+                    _ => format!("{} x{}", card, count),
+                })
+                .collect();
+            println!("| {}: {}", group, cards.join(", "));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Advice, AdviceType, Bonus, Country, Deck, Rules};
+
+    #[test]
+    fn cards_played_discarded_or_held_are_not_unseen() {
+        let deck: Deck = "country France 4 b\n\
+                          country Kenya 3 bc\n\
+                          country Peru 2 t\n\
+                          country Egypt 2 w\n\
+                          2x bonus beach\n\
+                          20x advice good money"
+            .parse()
+            .unwrap();
+        let mut board = Board::new_game_with_deck(2, Rules::default(), deck, 1).unwrap();
+        let country = |name: &str, score| Country::new(name, score, "b");
+        let money = Card::Advice(Advice::new(true, AdviceType::Money));
+
+        board.players[0].hand = vec![
+            Card::Country(country("France", 4)),
+            Card::Bonus(Bonus::Beach),
+        ];
+        board.players[1].hand = vec![
+            Card::Country(country("Kenya", 3)),
+            Card::Bonus(Bonus::Beach),
+        ];
+        board.players[1].pile = vec![country("Egypt", 2)];
+        board.past = vec![Card::Country(country("Peru", 2)), money.clone()];
+
+        // Only the other seat's hand and the rest of the deck are left
+        let unseen = board.unseen_groups(0);
+        assert_eq!(
+            unseen,
+            BTreeMap::from([
+                (
+                    Group::Continent(Continent::Africa),
+                    vec![(Card::Country(country("Kenya", 3)), 1)]
+                ),
+                (Group::Bonus, vec![(Card::Bonus(Bonus::Beach), 1)]),
+                (Group::Advice, vec![(money, 19)]),
+            ])
+        );
+    }
+}
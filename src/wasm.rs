@@ -0,0 +1,96 @@
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    Rules,
+    session::{Session, TurnCommand},
+};
+
+/// A game for JavaScript to drive, one seat's command at a time. Views, moves and events go
+/// back and forth as JSON strings in the same shapes the HTTP server uses.
+///
+/// ```js
+/// const game = new Game(2, 42, '[null, "greedy"]');
+/// const { legal_moves } = JSON.parse(game.legalMoves(0));
+/// game.applyMove(0, JSON.stringify(legal_moves[0]));
+/// ```
+#[wasm_bindgen]
+pub struct Game {
+    session: Session,
+}
+
+fn to_json(view: impl Serialize) -> String {
+    serde_json::to_string(&view).expect("Views always serialize")
+}
+
+#[wasm_bindgen]
+impl Game {
+    /// Deal a game for `players` seats. `seed` is a whole number that decides the deal, and
+    /// `bots` is an optional JSON array naming a strategy, or `null`, for each seat.
+    #[wasm_bindgen(constructor)]
+    pub fn new(players: usize, seed: f64, bots: Option<String>) -> Result<Game, JsError> {
+        let bots: Vec<Option<String>> = match bots {
+            Some(bots) => serde_json::from_str(&bots)?,
+            None => Vec::new(),
+        };
+        let session = Session::new(players, Rules::default(), seed as u64, &bots)?;
+        Ok(Self { session })
+    }
+
+    /// Whose turn it is, counting seats from 0
+    #[wasm_bindgen(getter)]
+    pub fn turn(&self) -> usize {
+        self.session.board().turn
+    }
+
+    #[wasm_bindgen(getter, js_name = isOver)]
+    pub fn is_over(&self) -> bool {
+        self.session.board().is_over()
+    }
+
+    /// Whether a bot plays `seat`, so nobody needs to make its moves
+    #[wasm_bindgen(js_name = isBot)]
+    pub fn is_bot(&self, seat: usize) -> Result<bool, JsError> {
+        self.check_seat(seat)?;
+        Ok(self.session.is_bot(seat))
+    }
+
+    /// Everything `seat` can see, including its own hand but nobody else's
+    pub fn view(&self, seat: usize) -> Result<String, JsError> {
+        self.check_seat(seat)?;
+        Ok(to_json(self.session.view(seat)))
+    }
+
+    /// What `seat` can do now, as `{"phase": "move", "legal_moves": [..]}`
+    #[wasm_bindgen(js_name = legalMoves)]
+    pub fn legal_moves(&self, seat: usize) -> Result<String, JsError> {
+        self.check_seat(seat)?;
+        Ok(to_json(self.session.moves(seat)))
+    }
+
+    /// Play a move like `{"command": "play", "index": 0}` for `seat`, or
+    /// `{"command": "draw", "from": "deck"}` once it has moved, and return what the seat
+    /// sees afterwards
+    #[wasm_bindgen(js_name = applyMove)]
+    pub fn apply_move(&mut self, seat: usize, command: &str) -> Result<String, JsError> {
+        self.check_seat(seat)?;
+        let command: TurnCommand = serde_json::from_str(command)?;
+        self.session
+            .play(seat, command)
+            .map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(to_json(self.session.view(seat)))
+    }
+
+    /// The events after the first `since`, as `{"events": [..], "next": 3, "over": false}`
+    pub fn events(&self, since: usize) -> String {
+        to_json(self.session.events(since))
+    }
+
+    fn check_seat(&self, seat: usize) -> Result<(), JsError> {
+        if seat < self.session.players() {
+            Ok(())
+        } else {
+            Err(JsError::new(&format!("no seat {}", seat)))
+        }
+    }
+}
@@ -0,0 +1,90 @@
+/* Plays a game through the C API, always making the first legal move and drawing from the
+ * deck, and checks that bad calls come back with the right codes. Exits with 0 when
+ * everything works. */
+
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "backpacker.h"
+
+#define CHECK(condition)                                                              \
+    do {                                                                              \
+        if (!(condition)) {                                                           \
+            fprintf(stderr, "%s:%d: %s failed (%s)\n", __FILE__, __LINE__, #condition, \
+                    bp_last_error());                                                 \
+            exit(1);                                                                  \
+        }                                                                             \
+    } while (0)
+
+/* The first object in the `legal_moves` array of `moves`, copied into `move` */
+static int first_move(const char *moves, char *move, size_t size) {
+    const char *start = strstr(moves, "\"legal_moves\":[{");
+    if (start == NULL) {
+        return 0;
+    }
+    start = strchr(start, '{');
+    const char *end = strchr(start, '}');
+    size_t length = (size_t)(end - start) + 1;
+    if (length >= size) {
+        return 0;
+    }
+    memcpy(move, start, length);
+    move[length] = '\0';
+    return 1;
+}
+
+int main(void) {
+    BpGame *game = NULL;
+
+    CHECK(bp_game_new(0, 1, &game) == BP_STATUS_NO_PLAYERS);
+    CHECK(strlen(bp_last_error()) > 0);
+    CHECK(bp_game_new(2, 1, NULL) == BP_STATUS_INVALID_ARGUMENT);
+    CHECK(bp_game_new(2, 42, &game) == BP_STATUS_OK);
+
+    char *json = NULL;
+    CHECK(bp_game_view(game, 0, &json) == BP_STATUS_OK);
+    CHECK(strstr(json, "\"hand\":[") != NULL);
+    bp_string_free(json);
+    CHECK(bp_game_view(game, 2, &json) == BP_STATUS_NO_SEAT);
+    CHECK(bp_game_turn(NULL) == SIZE_MAX);
+    CHECK(bp_game_is_over(NULL));
+
+    size_t turn = bp_game_turn(game);
+    CHECK(bp_game_apply_move(game, 1 - turn, "{\"command\": \"discard\", \"index\": 0}") ==
+          BP_STATUS_NOT_YOUR_TURN);
+    CHECK(bp_game_apply_move(game, turn, "{\"command\": \"play\", \"index\": 99}") ==
+          BP_STATUS_INVALID_INDEX);
+    CHECK(bp_game_apply_move(game, turn, "{\"command\": \"draw\", \"from\": \"deck\"}") ==
+          BP_STATUS_MOVE_FIRST);
+    CHECK(bp_game_apply_move(game, turn, "not json") == BP_STATUS_INVALID_ARGUMENT);
+
+    int turns = 0;
+    while (!bp_game_is_over(game)) {
+        CHECK(turns++ < 1000);
+        turn = bp_game_turn(game);
+
+        char *moves = NULL;
+        CHECK(bp_game_legal_moves(game, turn, &moves) == BP_STATUS_OK);
+        char move[128];
+        int found = first_move(moves, move, sizeof move);
+        bp_string_free(moves);
+
+        if (found) {
+            CHECK(bp_game_apply_move(game, turn, move) == BP_STATUS_OK);
+        } else {
+            CHECK(bp_game_apply_move(game, turn, "{\"command\": \"draw\", \"from\": \"deck\"}") ==
+                  BP_STATUS_OK);
+        }
+    }
+
+    CHECK(bp_game_apply_move(game, 0, "{\"command\": \"go_home\"}") == BP_STATUS_GAME_OVER);
+    CHECK(bp_game_events(game, 0, &json) == BP_STATUS_OK);
+    CHECK(strstr(json, "\"over\":true") != NULL);
+    bp_string_free(json);
+
+    bp_game_free(game);
+    printf("played %d turns\n", turns);
+    return 0;
+}
@@ -0,0 +1,59 @@
+use backpacker::{Board, Event, Move, Rules};
+use serde_json::Value;
+
+fn game() -> Board {
+    let rules = Rules {
+        draw_from_discard: true,
+    };
+    Board::new_seeded_game(2, rules, 5).unwrap()
+}
+
+/// The card in the last event of type `event`
+fn card_of(board: &Board, event: &str) -> Value {
+    board
+        .events()
+        .iter()
+        .map(|event| serde_json::to_value(event).unwrap())
+        .rfind(|value| value["event"] == event)
+        .unwrap_or_else(|| panic!("no {} event", event))["card"]
+        .clone()
+}
+
+#[test]
+fn a_discard_cannot_be_taken_back_the_same_turn() {
+    let mut board = game();
+    assert!(board.begin_turn());
+    board.apply_move(Move::Discard(0)).unwrap();
+    assert!(!board.can_take_discard());
+
+    // Asking for it anyway draws from the deck
+    board.end_turn(true);
+    assert!(
+        !board
+            .events()
+            .iter()
+            .any(|event| matches!(event, Event::TookDiscard { .. }))
+    );
+}
+
+#[test]
+fn the_next_player_can_take_the_discard() {
+    let mut board = game();
+    assert!(board.begin_turn());
+    board.apply_move(Move::Discard(0)).unwrap();
+    board.end_turn(false);
+    let discarded = card_of(&board, "discarded");
+    assert!(!discarded.is_null());
+    // Any move that doesn't put another card on the discard pile
+    assert!(board.begin_turn());
+    let mv = *board
+        .legal_moves()
+        .iter()
+        .find(|mv| matches!(mv, Move::Play(_)))
+        .expect("the second player has a card to play");
+    board.apply_move(mv).unwrap();
+    assert!(board.can_take_discard());
+
+    board.end_turn(true);
+    assert_eq!(card_of(&board, "took_discard"), discarded);
+}
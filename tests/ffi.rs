@@ -0,0 +1,58 @@
+use std::{fs, path::PathBuf, process::Command};
+
+/// Build the cdylib, compile `tests/c/play.c` against it and the generated header, and run it
+#[test]
+fn c_program_plays_a_game() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // Cargo doesn't link the cdylib for tests, so build it where it can't be mixed up with
+    // one built with other features
+    let target = root.join("target/ffi-test");
+    let built = Command::new(env!("CARGO"))
+        .args(["build", "--lib", "--no-default-features", "--features", "ffi"])
+        .arg("--target-dir")
+        .arg(&target)
+        .current_dir(&root)
+        .status()
+        .unwrap();
+    assert!(built.success());
+
+    let lib_dir = target.join("debug");
+    let program = lib_dir.join("play");
+    let compiled = Command::new("cc")
+        .arg(root.join("tests/c/play.c"))
+        .arg("-I")
+        .arg(env!("OUT_DIR"))
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg("-lbackpacker")
+        .arg("-o")
+        .arg(&program)
+        .status()
+        .expect("a C compiler is installed as `cc`");
+    assert!(compiled.success());
+
+    let output = Command::new(&program)
+        .env("LD_LIBRARY_PATH", &lib_dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("played "));
+}
+
+/// The header in `include/` is for people who don't build with Cargo, so it has to be kept
+/// up to date by hand
+#[test]
+fn the_shipped_header_is_up_to_date() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let shipped = fs::read_to_string(root.join("include/backpacker.h")).unwrap();
+    let generated =
+        fs::read_to_string(PathBuf::from(env!("OUT_DIR")).join("backpacker.h")).unwrap();
+    assert!(
+        shipped == generated,
+        "include/backpacker.h is out of date, rebuild with BACKPACKER_WRITE_HEADER=1"
+    );
+}
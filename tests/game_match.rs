@@ -0,0 +1,126 @@
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, Lines, Write},
+    path::{Path, PathBuf},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+use serde_json::Value;
+
+/// A match being played over JSON lines
+struct Game {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    stdout: Lines<BufReader<ChildStdout>>,
+}
+
+impl Game {
+    fn start(args: &[&str], profiles: &Path) -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_backpacker"))
+            .args(["play", "--format", "jsonl"])
+            .args(args)
+            .arg("--profiles")
+            .arg(profiles)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let stdin = child.stdin.take();
+        let stdout = BufReader::new(child.stdout.take().unwrap()).lines();
+        Self {
+            child,
+            stdin,
+            stdout,
+        }
+    }
+
+    fn next(&mut self) -> Option<Value> {
+        let line = self.stdout.next()?.unwrap();
+        Some(serde_json::from_str(&line).unwrap())
+    }
+
+    /// Answer a move prompt with a discard, so the game can't get stuck
+    fn answer(&mut self, prompt: &Value) {
+        let moves = prompt["legal_moves"].as_array().unwrap();
+        let discard = moves
+            .iter()
+            .find(|mv| mv["command"] == "discard")
+            .unwrap_or(&moves[0]);
+        writeln!(self.stdin.as_mut().unwrap(), "{}", discard).unwrap();
+    }
+
+    /// Close stdin, leaving the game, and wait for the match to be saved
+    fn leave(mut self) {
+        drop(self.stdin.take());
+        assert!(self.child.wait().unwrap().success());
+    }
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!(
+        "backpacker-match-{}-{}.json",
+        name,
+        std::process::id()
+    ))
+}
+
+fn saved(path: &Path) -> Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn a_match_left_part_way_through_a_game_carries_on_where_it_was() {
+    let save = temp_path("save");
+    let profiles = temp_path("profiles");
+    let save_arg = save.to_str().unwrap();
+
+    // Play all of the first game, then one turn of the second
+    let mut game = Game::start(&["--games", "2", "--save", save_arg], &profiles);
+    let mut number = 0;
+    let mut prompts = Vec::new();
+    let mut state = Value::Null;
+    while let Some(message) = game.next() {
+        match message["type"].as_str().unwrap() {
+            "match_game" => number = message["game"].as_u64().unwrap(),
+            "state" => state = message,
+            "prompt" if number == 2 && prompts.len() == 1 => break,
+            "prompt" => {
+                if number == 2 {
+                    prompts.push(message["seat"].clone());
+                }
+                game.answer(&message);
+            }
+            _ => {}
+        }
+    }
+    game.leave();
+    // The second game is started by the second seat
+    assert_eq!(prompts, [1]);
+
+    let left = saved(&save);
+    assert_eq!(left["results"].as_array().unwrap().len(), 1);
+    assert!(!left["current"].is_null());
+
+    // Carrying on is still the second game, at the turn that was left
+    let mut game = Game::start(&["--resume", save_arg], &profiles);
+    let mut resumed = Vec::new();
+    let mut first_state = None;
+    while let Some(message) = game.next() {
+        match message["type"].as_str().unwrap() {
+            "match_game" => resumed.push(message["game"].clone()),
+            "state" if first_state.is_none() => first_state = Some(message),
+            "prompt" => game.answer(&message),
+            _ => {}
+        }
+    }
+    game.leave();
+    assert_eq!(resumed, [2]);
+    assert_eq!(first_state, Some(state));
+
+    let finished = saved(&save);
+    assert_eq!(finished["results"].as_array().unwrap().len(), 2);
+    assert!(finished["current"].is_null());
+
+    let _ = fs::remove_file(&save);
+    let _ = fs::remove_file(&profiles);
+}
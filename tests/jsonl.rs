@@ -0,0 +1,94 @@
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, Write},
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+};
+
+use serde_json::{Value, json};
+
+/// Start a two player game in JSON-lines mode, keeping any profiles out of the way
+fn start(name: &str) -> (Child, PathBuf) {
+    let profiles = env::temp_dir().join(format!(
+        "backpacker-jsonl-{}-{}.json",
+        name,
+        std::process::id()
+    ));
+    let game = Command::new(env!("CARGO_BIN_EXE_backpacker"))
+        .args(["play", "--format", "jsonl", "--seed", "5", "--profiles"])
+        .arg(&profiles)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    (game, profiles)
+}
+
+/// The answer to a prompt: a discard when there's one, so the game can't get stuck, and
+/// always drawing from the deck
+fn answer(prompt: &Value) -> Value {
+    if prompt["prompt"] == "draw" {
+        return json!({ "command": "draw", "from": "deck" });
+    }
+    let moves = prompt["legal_moves"].as_array().unwrap();
+    moves
+        .iter()
+        .find(|mv| mv["command"] == "discard")
+        .unwrap_or(&moves[0])
+        .clone()
+}
+
+#[test]
+fn a_whole_game_is_played_over_json_lines() {
+    let (mut game, profiles) = start("whole");
+    let mut stdin = game.stdin.take().unwrap();
+    let stdout = BufReader::new(game.stdout.take().unwrap());
+
+    let mut types = Vec::new();
+    let mut prompt = Value::Null;
+    for line in stdout.lines() {
+        let message: Value = serde_json::from_str(&line.unwrap()).unwrap();
+        let kind = message["type"].as_str().unwrap().to_string();
+        match kind.as_str() {
+            // The first prompt gets something that isn't a command at all
+            "prompt" if types.len() == 2 => {
+                writeln!(stdin, "not json").unwrap();
+                prompt = message;
+            }
+            "prompt" => writeln!(stdin, "{}", answer(&message)).unwrap(),
+            // which is answered properly once it's been refused, without asking again
+            "error" => writeln!(stdin, "{}", answer(&prompt)).unwrap(),
+            _ => {}
+        }
+        types.push(kind);
+    }
+    assert!(game.wait().unwrap().success());
+    let _ = fs::remove_file(&profiles);
+
+    assert_eq!(
+        types[..5],
+        ["game_started", "state", "prompt", "error", "event"]
+    );
+    assert_eq!(types.last().unwrap(), "game_over");
+    assert_eq!(types.iter().filter(|kind| *kind == "game_over").count(), 1);
+}
+
+#[test]
+fn closing_input_abandons_the_game() {
+    let (mut game, profiles) = start("abandon");
+    drop(game.stdin.take());
+    let output = game.wait_with_output().unwrap();
+    let _ = fs::remove_file(&profiles);
+
+    assert!(output.status.success());
+    let types: Vec<String> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| {
+            let message: Value = serde_json::from_str(line).unwrap();
+            message["type"].as_str().unwrap().to_string()
+        })
+        .collect();
+    // The game stops at the first prompt, without finishing
+    assert_eq!(types, ["game_started", "state", "prompt"]);
+}
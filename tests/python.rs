@@ -0,0 +1,56 @@
+use std::{fs, path::PathBuf, process::Command};
+
+/// Build the Python module and run `tests/python/test_game.py` against it
+#[test]
+fn python_module_plays_games() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // As with the C API, the cdylib is built on its own so it can't be mixed up with one
+    // built with other features
+    let target = root.join("target/python-test");
+    let built = Command::new(env!("CARGO"))
+        .args([
+            "build",
+            "--lib",
+            "--no-default-features",
+            "--features",
+            "python",
+        ])
+        .arg("--target-dir")
+        .arg(&target)
+        .current_dir(&root)
+        .status()
+        .unwrap();
+    assert!(built.success());
+
+    // Python imports `backpacker` from a file named after the module
+    let module_dir = target.join("module");
+    fs::create_dir_all(&module_dir).unwrap();
+    fs::copy(
+        target.join("debug/libbackpacker.so"),
+        module_dir.join("backpacker.so"),
+    )
+    .unwrap();
+
+    // Built without `extension-module`, the module links to libpython, which has to be found
+    let python = "python3";
+    let lib_dir = Command::new(python)
+        .args([
+            "-c",
+            "import sysconfig; print(sysconfig.get_config_var('LIBDIR'))",
+        ])
+        .output()
+        .expect("python3 is installed");
+    let lib_dir = String::from_utf8(lib_dir.stdout).unwrap();
+
+    let output = Command::new(python)
+        .arg(root.join("tests/python/test_game.py"))
+        .env("PYTHONPATH", &module_dir)
+        .env("LD_LIBRARY_PATH", lib_dir.trim())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
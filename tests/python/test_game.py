@@ -0,0 +1,145 @@
+"""Drives the Python module the way a notebook would. Exits with an error if anything fails."""
+
+import copy
+import random
+
+import backpacker
+
+
+def play_first_moves(game):
+    """Make the first legal move, drawing from the deck if asked, until the game is over"""
+    turns = 0
+    while not game.is_over():
+        assert turns < 1000, "the game should have finished"
+        turns += 1
+        seat = game.turn
+        if game.phase == "draw":
+            game.apply_move(seat, {"command": "draw", "from": "deck"})
+        else:
+            game.apply_move(seat, game.legal_moves(seat)[0])
+    return turns
+
+
+def test_views_and_moves():
+    game = backpacker.Game(2, seed=7)
+    seat = game.turn
+    view = game.view(seat)
+    assert view["seat"] == seat
+    assert len(view["hand"]) == 5
+    assert all("hand" not in player for player in view["players"])
+    assert game.legal_moves(1 - seat) == []
+
+    try:
+        game.apply_move(seat, {"command": "play", "index": 99})
+    except ValueError:
+        pass
+    else:
+        raise AssertionError("an illegal move should raise ValueError")
+
+    assert play_first_moves(game) > 0
+    assert game.phase == "game_over"
+    assert game.winners()
+
+
+def test_clones_are_independent():
+    game = backpacker.Game(2, seed=3, rules={"draw_from_discard": True})
+    clone = game.clone()
+    seat = game.turn
+    game.apply_move(seat, game.legal_moves(seat)[0])
+    assert len(game.events()) > len(clone.events())
+    assert clone.legal_moves(seat) != []
+
+    # Copies play out the same way as the game they came from
+    play_first_moves(clone)
+    later = copy.deepcopy(clone)
+    assert later.scores() == clone.scores()
+    assert later.events() == clone.events()
+
+
+def test_bots_take_their_turns():
+    game = backpacker.Game(2, seed=5, bots=[None, "greedy"])
+    assert game.turn == 0
+    game.apply_move(0, game.legal_moves(0)[0])
+    if game.phase == "draw":
+        game.apply_move(0, {"command": "draw", "from": "deck"})
+    assert game.turn == 0
+    assert any(event.get("seat") == 1 for event in game.events())
+
+
+def test_headless_games():
+    assert "greedy" in backpacker.strategy_names()
+
+    class LastMove:
+        def choose_move(self, view, moves):
+            assert len(view["hand"]) == 5
+            return moves[-1]
+
+        def take_discard(self, view):
+            return False
+
+    result = backpacker.play_game(["greedy", LastMove(), lambda view, moves: moves[0]], seed=11)
+    assert len(result["scores"]) == 3
+    assert result["winners"]
+    assert result["events"]
+
+    # The same seed gives the same game
+    again = backpacker.play_game(["greedy", LastMove(), lambda view, moves: moves[0]], seed=11)
+    assert again == result
+
+
+def test_strategy_errors_are_raised():
+    def broken(view, moves):
+        raise RuntimeError("boom")
+
+    try:
+        backpacker.play_game([broken, "random"], seed=1)
+    except RuntimeError as e:
+        assert str(e) == "boom"
+    else:
+        raise AssertionError("the strategy's error should be raised")
+
+    try:
+        backpacker.play_game([lambda view, moves: {"command": "play", "index": 42}, "random"])
+    except ValueError as e:
+        assert "isn't a legal move" in str(e)
+    else:
+        raise AssertionError("an illegal move should raise ValueError")
+
+
+def test_environment():
+    env = backpacker.Env(3, opponent="greedy")
+    observation = env.reset(seed=9)
+    assert len(observation) == env.observation_size
+    assert env.action_count == 13
+
+    rng = random.Random(9)
+    total, done, steps = 0.0, False, 0
+    while not done:
+        mask = env.action_mask()
+        assert len(mask) == env.action_count and any(mask)
+        for action in [action for action, legal in enumerate(mask) if not legal]:
+            try:
+                env.step(action)
+            except ValueError:
+                pass
+            else:
+                raise AssertionError(f"masked action {action} should raise ValueError")
+
+        action = rng.choice([action for action, legal in enumerate(mask) if legal])
+        observation, reward, done = env.step(action)
+        assert len(observation) == env.observation_size
+        assert reward >= 0
+        total += reward
+        steps += 1
+        assert steps < 2000, "the game should have finished"
+
+    assert not any(env.action_mask())
+    # With this seed, random play banks some points over the game
+    assert total > 0
+
+
+if __name__ == "__main__":
+    for name, test in list(globals().items()):
+        if name.startswith("test_"):
+            test()
+            print(name, "ok")
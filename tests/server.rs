@@ -0,0 +1,245 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use backpacker::GameServer;
+use serde_json::{Value, json};
+use ureq::Agent;
+
+/// Talks to a server started on a free localhost port
+struct Client {
+    agent: Agent,
+    base: String,
+}
+
+impl Client {
+    fn start() -> Self {
+        let server = GameServer::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", server.address());
+        thread::spawn(move || server.run());
+
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .build()
+            .into();
+        Self { agent, base }
+    }
+
+    fn get(&self, path: &str, token: Option<&str>) -> (u16, Value) {
+        let mut request = self.agent.get(format!("{}{}", self.base, path));
+        if let Some(token) = token {
+            request = request.header("Authorization", format!("Bearer {}", token));
+        }
+        let mut response = request.call().unwrap();
+        let status = response.status().as_u16();
+        (status, response.body_mut().read_json().unwrap())
+    }
+
+    fn post(&self, path: &str, token: Option<&str>, body: Value) -> (u16, Value) {
+        let mut request = self.agent.post(format!("{}{}", self.base, path));
+        if let Some(token) = token {
+            request = request.header("Authorization", format!("Bearer {}", token));
+        }
+        let mut response = request.send_json(body).unwrap();
+        let status = response.status().as_u16();
+        (status, response.body_mut().read_json().unwrap())
+    }
+
+    fn create(&self, body: Value) -> u64 {
+        let (status, response) = self.post("/games", None, body);
+        assert_eq!(status, 200, "{}", response);
+        response["game"].as_u64().unwrap()
+    }
+
+    fn join(&self, game: u64, seat: usize) -> String {
+        let path = format!("/games/{}/seats/{}", game, seat);
+        let (status, response) =
+            self.post(&path, None, json!({ "name": format!("Seat {}", seat) }));
+        assert_eq!(status, 200, "{}", response);
+        response["token"].as_str().unwrap().to_string()
+    }
+
+    /// Make the first legal move for `seat`, drawing from the deck if asked
+    fn play_first_move(&self, game: u64, seat: usize, token: &str) -> Value {
+        let path = format!("/games/{}/seats/{}/moves", game, seat);
+        let (status, moves) = self.get(&path, Some(token));
+        assert_eq!(status, 200, "{}", moves);
+        let mv = moves["legal_moves"][0].clone();
+
+        let (status, mut view) = self.post(&path, Some(token), mv);
+        assert_eq!(status, 200, "{}", view);
+        if view["phase"] == "draw" && view["turn"] == seat {
+            let (status, after) = self.post(
+                &path,
+                Some(token),
+                json!({ "command": "draw", "from": "deck" }),
+            );
+            assert_eq!(status, 200, "{}", after);
+            view = after;
+        }
+        view
+    }
+}
+
+#[test]
+fn seats_see_their_own_hand_only() {
+    let client = Client::start();
+    let game = client.create(json!({ "players": 2, "seed": 7 }));
+    let tokens = [client.join(game, 0), client.join(game, 1)];
+
+    let (status, view) = client.get(&format!("/games/{}/seats/0", game), Some(&tokens[0]));
+    assert_eq!(status, 200, "{}", view);
+    assert_eq!(view["seat"], 0);
+    assert_eq!(view["phase"], "move");
+    assert_eq!(view["hand"].as_array().unwrap().len(), 5);
+    assert_eq!(view["players"][1]["name"], "Seat 1");
+    for player in view["players"].as_array().unwrap() {
+        assert!(player.get("hand").is_none());
+    }
+
+    // Another seat's view needs that seat's token
+    let (status, _) = client.get(&format!("/games/{}/seats/1", game), Some(&tokens[0]));
+    assert_eq!(status, 403);
+    let (status, _) = client.get(&format!("/games/{}/seats/1", game), None);
+    assert_eq!(status, 403);
+}
+
+#[test]
+fn moves_are_checked() {
+    let client = Client::start();
+    let game = client.create(json!({ "players": 2, "seed": 7 }));
+    let tokens = [client.join(game, 0), client.join(game, 1)];
+
+    let (status, _) = client.post(&format!("/games/{}/seats/0", game), None, json!({}));
+    assert_eq!(status, 409, "seats can only be joined once");
+
+    let (_, view) = client.get(&format!("/games/{}/seats/0", game), Some(&tokens[0]));
+    let turn = view["turn"].as_u64().unwrap() as usize;
+    let waiting = 1 - turn;
+
+    let (status, moves) = client.get(
+        &format!("/games/{}/seats/{}/moves", game, waiting),
+        Some(&tokens[waiting]),
+    );
+    assert_eq!(status, 200);
+    assert_eq!(moves["legal_moves"], json!([]));
+
+    let (status, _) = client.post(
+        &format!("/games/{}/seats/{}/moves", game, waiting),
+        Some(&tokens[waiting]),
+        json!({ "command": "discard", "index": 0 }),
+    );
+    assert_eq!(status, 409, "only the current seat can move");
+
+    let (status, error) = client.post(
+        &format!("/games/{}/seats/{}/moves", game, turn),
+        Some(&tokens[turn]),
+        json!({ "command": "play", "index": 99 }),
+    );
+    assert_eq!(status, 422);
+    assert!(error["error"].is_string());
+
+    let (status, _) = client.post(
+        &format!("/games/{}/seats/{}/moves", game, turn),
+        Some(&tokens[turn]),
+        json!({ "command": "draw", "from": "deck" }),
+    );
+    assert_eq!(status, 409, "a move comes before drawing");
+
+    let (status, _) = client.get("/games/99/seats/0", Some(&tokens[0]));
+    assert_eq!(status, 404);
+    let (status, _) = client.get(&format!("/games/{}/seats/5", game), Some(&tokens[0]));
+    assert_eq!(status, 404);
+}
+
+#[test]
+fn a_move_passes_the_turn_on() {
+    let client = Client::start();
+    let game = client.create(json!({ "players": 2, "seed": 3 }));
+    let tokens = [client.join(game, 0), client.join(game, 1)];
+
+    let (_, view) = client.get(&format!("/games/{}/seats/0", game), Some(&tokens[0]));
+    let turn = view["turn"].as_u64().unwrap() as usize;
+    let view = client.play_first_move(game, turn, &tokens[turn]);
+    assert_eq!(view["turn"], 1 - turn);
+    assert_eq!(view["hand"].as_array().unwrap().len(), 5);
+    assert!(view["events"].as_u64().unwrap() > 0);
+}
+
+#[test]
+fn events_wait_for_the_next_move() {
+    let client = Client::start();
+    let game = client.create(json!({ "players": 2, "seed": 3 }));
+    let tokens = [client.join(game, 0), client.join(game, 1)];
+
+    let (status, events) = client.get(&format!("/games/{}/events?wait=0", game), None);
+    assert_eq!(status, 200);
+    let since = events["next"].as_u64().unwrap();
+
+    let waiter = {
+        let client = Client {
+            agent: client.agent.clone(),
+            base: client.base.clone(),
+        };
+        thread::spawn(move || {
+            let started = Instant::now();
+            let path = format!("/games/{}/events?since={}&wait=20", game, since);
+            let (status, events) = client.get(&path, None);
+            (status, events, started.elapsed())
+        })
+    };
+
+    thread::sleep(Duration::from_millis(200));
+    let (_, view) = client.get(&format!("/games/{}/seats/0", game), Some(&tokens[0]));
+    let turn = view["turn"].as_u64().unwrap() as usize;
+    client.play_first_move(game, turn, &tokens[turn]);
+
+    let (status, events, waited) = waiter.join().unwrap();
+    assert_eq!(status, 200);
+    assert!(waited < Duration::from_secs(10), "waited {:?}", waited);
+    let events = events["events"].as_array().unwrap();
+    assert!(!events.is_empty());
+    assert_eq!(events[0]["seat"], turn);
+}
+
+#[test]
+fn bots_take_their_turns() {
+    let client = Client::start();
+    let game = client.create(json!({ "players": 2, "seed": 5, "bots": [null, "greedy"] }));
+    let token = client.join(game, 0);
+
+    let (status, _) = client.post(&format!("/games/{}/seats/1", game), None, json!({}));
+    assert_eq!(status, 409, "bot seats can't be joined");
+
+    // Whoever goes first, it's always the person's turn when they look
+    let (_, view) = client.get(&format!("/games/{}/seats/0", game), Some(&token));
+    assert_eq!(view["turn"], 0);
+    let view = client.play_first_move(game, 0, &token);
+    assert_eq!(view["turn"], 0);
+
+    let (_, events) = client.get(&format!("/games/{}/events?wait=0", game), None);
+    let events = events["events"].as_array().unwrap();
+    assert!(events.iter().any(|event| event["seat"] == 1));
+}
+
+#[test]
+fn games_play_to_the_end() {
+    let client = Client::start();
+    let game =
+        client.create(json!({ "players": 2, "seed": 11, "rules": { "draw_from_discard": true } }));
+    let tokens = [client.join(game, 0), client.join(game, 1)];
+
+    for _ in 0..500 {
+        let (_, view) = client.get(&format!("/games/{}/seats/0", game), Some(&tokens[0]));
+        if view["phase"] == "game_over" {
+            let (_, events) = client.get(&format!("/games/{}/events?since=0&wait=0", game), None);
+            assert_eq!(events["over"], true);
+            assert_eq!(view["deck"], 0);
+            return;
+        }
+        let turn = view["turn"].as_u64().unwrap() as usize;
+        client.play_first_move(game, turn, &tokens[turn]);
+    }
+    panic!("the game should have finished");
+}
@@ -0,0 +1,110 @@
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, Lines, Write},
+    net::TcpStream,
+    process::{ChildStdin, ChildStdout, Command, Stdio},
+};
+
+use serde_json::Value;
+
+const REVEAL_DELAY: usize = 10;
+
+/// Read JSON lines until one of type `kind` turns up
+fn next_of(lines: &mut Lines<BufReader<ChildStdout>>, kind: &str) -> Value {
+    for line in lines {
+        let message: Value = serde_json::from_str(&line.unwrap()).unwrap();
+        if message["type"] == kind {
+            return message;
+        }
+    }
+    panic!("the game ended without a {} message", kind);
+}
+
+/// Answer the next move prompt with a discard, so the game carries on
+fn play_turn(lines: &mut Lines<BufReader<ChildStdout>>, stdin: &mut ChildStdin) {
+    let prompt = next_of(lines, "prompt");
+    let moves = prompt["legal_moves"].as_array().unwrap();
+    let discard = moves
+        .iter()
+        .find(|mv| mv["command"] == "discard")
+        .unwrap_or(&moves[0]);
+    writeln!(stdin, "{}", discard).unwrap();
+}
+
+/// Whether `value` holds anyone's cards anywhere in it
+fn has_hand(value: &Value) -> bool {
+    match value {
+        Value::Object(fields) => fields
+            .iter()
+            .any(|(key, value)| key == "hand" || key == "hands" || has_hand(value)),
+        Value::Array(values) => values.iter().any(has_hand),
+        _ => false,
+    }
+}
+
+#[test]
+fn hands_are_hidden_until_the_game_is_abandoned() {
+    let profiles = env::temp_dir().join(format!("backpacker-spectate-{}.json", std::process::id()));
+    let mut game = Command::new(env!("CARGO_BIN_EXE_backpacker"))
+        .args([
+            "play", "--format", "jsonl", "--seed", "3", "--bot", "2=greedy",
+        ])
+        .args(["--spectate", "127.0.0.1:0", "--reveal-delay"])
+        .arg(REVEAL_DELAY.to_string())
+        .arg("--profiles")
+        .arg(&profiles)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut stderr = BufReader::new(game.stderr.take().unwrap()).lines();
+    let address = stderr
+        .find_map(|line| {
+            line.unwrap()
+                .strip_prefix("Spectators can watch at ")
+                .map(str::to_string)
+        })
+        .expect("the game says where to watch");
+    let spectator = TcpStream::connect(&address).unwrap();
+    let mut watched = BufReader::new(spectator).lines().map(|line| {
+        let line = line.unwrap();
+        serde_json::from_str::<Value>(&line).unwrap()
+    });
+    // The game waits for the first move, so this is the start of the game or a catch-up
+    let first = watched.next().unwrap();
+    assert!(first["type"] == "game_started" || first["type"] == "public_state");
+
+    let mut stdin = game.stdin.take().unwrap();
+    let mut stdout = BufReader::new(game.stdout.take().unwrap()).lines();
+    for _ in 0..2 {
+        play_turn(&mut stdout, &mut stdin);
+    }
+    // Wait for the bot's turn to finish too, then leave
+    next_of(&mut stdout, "prompt");
+    drop(stdin);
+    assert!(game.wait().unwrap().success());
+    let _ = fs::remove_file(&profiles);
+
+    let mut seen: Vec<Value> = vec![first];
+    seen.extend(watched);
+    let types: Vec<&str> = seen
+        .iter()
+        .map(|message| message["type"].as_str().unwrap())
+        .collect();
+    assert_eq!(types.last(), Some(&"game_over"));
+
+    // Nothing shows a hand until the end, when every turn's hands are shown
+    let reveals = types.iter().filter(|&&kind| kind == "reveal").count();
+    let turns = types.iter().filter(|&&kind| kind == "public_state").count() - 1;
+    assert!(turns < REVEAL_DELAY);
+    assert_eq!(reveals, turns);
+    let before_reveals = &seen[..seen.len() - 1 - reveals];
+    assert!(before_reveals.iter().all(|message| !has_hand(message)));
+    assert!(
+        seen[seen.len() - 1 - reveals..seen.len() - 1]
+            .iter()
+            .all(|message| message["type"] == "reveal")
+    );
+}
@@ -0,0 +1,117 @@
+use std::{
+    sync::{Arc, Mutex, mpsc},
+    time::Duration,
+};
+
+use backpacker::{Board, Clock, DefaultAction, Event, FakeClock, Move, Rules, Strategy, TurnTimer};
+
+const LIMIT: Duration = Duration::from_secs(30);
+
+/// Plays the first legal move, remembering every move it was asked for
+#[derive(Clone, Default)]
+struct FirstMove {
+    chosen: Arc<Mutex<Vec<Move>>>,
+}
+
+impl Strategy for FirstMove {
+    fn choose_move(&mut self, _board: &Board, moves: &[Move]) -> Move {
+        self.chosen.lock().unwrap().push(moves[0]);
+        moves[0]
+    }
+}
+
+/// A two player game whose first turn has started, with the time to run it down
+fn timed_game(default: DefaultAction) -> (Board, Arc<FakeClock>) {
+    let clock = Arc::new(FakeClock::new());
+    let mut timer = TurnTimer::new(LIMIT, default).with_clock(clock.clone());
+    timer.start();
+
+    let mut board = Board::new_seeded_game(2, Rules::default(), 7).unwrap();
+    assert!(board.begin_turn());
+    board.set_turn_timer(Some(timer));
+    (board, clock)
+}
+
+#[test]
+fn read_line_returns_a_line_given_in_time() {
+    let (sender, receiver) = mpsc::channel();
+    let clock = Arc::new(FakeClock::new());
+    let mut timer = TurnTimer::new(LIMIT, DefaultAction::DiscardLowest)
+        .with_clock(clock.clone())
+        .with_input(receiver);
+    timer.start();
+
+    sender.send(Ok("home\n".to_string())).unwrap();
+    assert_eq!(timer.read_line().unwrap().unwrap(), "home\n");
+    assert!(!timer.expired());
+}
+
+#[test]
+fn read_line_gives_up_when_the_turn_runs_out() {
+    let (_sender, receiver) = mpsc::channel();
+    let clock = Arc::new(FakeClock::new());
+    let mut timer = TurnTimer::new(LIMIT, DefaultAction::DiscardLowest)
+        .with_clock(clock.clone())
+        .with_input(receiver);
+    timer.start();
+
+    // Waiting on the fake clock moves it on, so this doesn't take 30 seconds
+    assert!(timer.read_line().is_none());
+    assert!(timer.expired());
+    assert_eq!(clock.now(), LIMIT);
+}
+
+#[test]
+fn read_line_treats_closed_input_as_end_of_input() {
+    let (sender, receiver) = mpsc::channel();
+    let mut timer = TurnTimer::new(LIMIT, DefaultAction::DiscardLowest)
+        .with_clock(Arc::new(FakeClock::new()))
+        .with_input(receiver);
+    timer.start();
+
+    drop(sender);
+    assert_eq!(timer.read_line().unwrap().unwrap(), "");
+}
+
+#[test]
+fn running_out_of_time_discards_and_draws() {
+    let (mut board, clock) = timed_game(DefaultAction::DiscardLowest);
+
+    clock.advance(LIMIT - Duration::from_secs(1));
+    assert!(!board.turn_timed_out());
+    clock.advance(Duration::from_secs(1));
+    assert!(board.turn_timed_out());
+
+    board.time_out();
+    let events = board.events();
+    assert!(matches!(
+        events,
+        [
+            ..,
+            Event::TimedOut { seat: 0 },
+            Event::Discarded { seat: 0, .. },
+            Event::Drew { seat: 0, .. }
+        ]
+    ));
+    assert_eq!(board.current_seat(), 1);
+}
+
+#[test]
+fn running_out_of_time_lets_the_bot_move() {
+    let bot = FirstMove::default();
+    let chosen = bot.chosen.clone();
+    let (mut board, clock) = timed_game(DefaultAction::Bot(Box::new(bot)));
+    let first = board.legal_moves()[0];
+
+    clock.advance(LIMIT);
+    board.time_out();
+
+    assert_eq!(*chosen.lock().unwrap(), vec![first]);
+    assert!(
+        board
+            .events()
+            .iter()
+            .any(|event| matches!(event, Event::TimedOut { seat: 0 }))
+    );
+    assert_eq!(board.current_seat(), 1);
+}